@@ -0,0 +1,274 @@
+//! Files attached to items. Attached files are copied into a folder next to
+//! the workspace data file, so every workspace has its own.
+
+use crate::i18n::{tr, Msg};
+use crate::links::open_path;
+use crate::{Item, ProductionManager};
+use eframe::egui;
+use serde::{Deserialize, Serialize};
+use std::fs;
+use std::io;
+use std::path::{Path, PathBuf};
+
+const IMAGE_EXTENSIONS: [&str; 9] = ["png", "jpg", "jpeg", "gif", "bmp", "webp", "ico", "svg", "tiff"];
+const THUMBNAIL_HEIGHT: f32 = 72.0;
+const GALLERY_SIZE: f32 = 96.0;
+/// Folder next to an exported Markdown file that receives the attachments.
+pub const EXPORT_DIR: &str = "attachments";
+
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq)]
+pub struct Attachment {
+    /// File name inside the attachments folder.
+    pub file: String,
+    /// Original file name, shown to the user.
+    pub name: String,
+}
+
+impl Attachment {
+    pub fn is_image(&self) -> bool {
+        Path::new(&self.file)
+            .extension()
+            .and_then(|e| e.to_str())
+            .is_some_and(|e| IMAGE_EXTENSIONS.contains(&e.to_ascii_lowercase().as_str()))
+    }
+}
+
+/// The attachments folder of the workspace stored at `data_path`.
+pub fn dir_for(data_path: &Path) -> PathBuf {
+    let stem = data_path.file_stem().and_then(|s| s.to_str()).unwrap_or("data");
+    data_path.with_file_name(format!("{}-attachments", stem))
+}
+
+fn image_uri(dir: &Path, attachment: &Attachment) -> String {
+    format!("file://{}", dir.join(&attachment.file).display())
+}
+
+/// Unique stored name that still ends in the original file name.
+fn stored_name(name: &str) -> String {
+    let id = uuid::Uuid::new_v4().simple().to_string();
+    let safe: String = name
+        .chars()
+        .map(|c| if c.is_alphanumeric() || matches!(c, '.' | '-' | '_') { c } else { '_' })
+        .collect();
+    format!("{}-{}", &id[..8], safe)
+}
+
+/// Copies `src` into `dir`.
+pub fn import(dir: &Path, src: &Path) -> io::Result<Attachment> {
+    let name = src.file_name().and_then(|n| n.to_str()).unwrap_or("file").to_string();
+    let file = stored_name(&name);
+    fs::create_dir_all(dir)?;
+    fs::copy(src, dir.join(&file))?;
+    Ok(Attachment { file, name })
+}
+
+/// Saves the image on the clipboard as a PNG in `dir`. `None` when the
+/// clipboard holds no image.
+pub fn paste_image(dir: &Path) -> Option<Attachment> {
+    let image = arboard::Clipboard::new().ok()?.get_image().ok()?;
+    let buffer = image::RgbaImage::from_raw(image.width as u32, image.height as u32, image.bytes.into_owned())?;
+    let name = format!("clipboard-{}.png", chrono::Local::now().format("%Y%m%d-%H%M%S"));
+    let file = stored_name(&name);
+    fs::create_dir_all(dir).ok()?;
+    buffer.save(dir.join(&file)).ok()?;
+    Some(Attachment { file, name })
+}
+
+/// Deletes the stored files of `attachments`.
+pub fn remove_files(dir: &Path, attachments: &[Attachment]) {
+    for attachment in attachments {
+        fs::remove_file(dir.join(&attachment.file)).ok();
+    }
+}
+
+/// Copies every file of one attachments folder into another.
+pub fn copy_all(from: &Path, to: &Path) {
+    let Ok(entries) = fs::read_dir(from) else {
+        return;
+    };
+    if fs::create_dir_all(to).is_err() {
+        return;
+    }
+    for entry in entries.flatten() {
+        fs::copy(entry.path(), to.join(entry.file_name())).ok();
+    }
+}
+
+/// Image embeds and file links for a Markdown export, relative to
+/// [`EXPORT_DIR`].
+pub fn to_markdown(attachments: &[Attachment]) -> String {
+    let mut md = String::new();
+    for attachment in attachments {
+        let target = format!("{}/{}", EXPORT_DIR, attachment.file);
+        if attachment.is_image() {
+            md.push_str(&format!("![{}]({})\n", attachment.name, target));
+        } else {
+            md.push_str(&format!("- 📎 [{}]({})\n", attachment.name, target));
+        }
+    }
+    if !md.is_empty() {
+        md.push('\n');
+    }
+    md
+}
+
+/// Thumbnail of the first attached image, if any.
+pub fn render_thumbnail(ui: &mut egui::Ui, dir: &Path, item: &Item) {
+    let Some(first) = item.attachments.iter().find(|a| a.is_image()) else {
+        return;
+    };
+    ui.add_space(4.0);
+    let more = item.attachments.len() - 1;
+    ui.horizontal(|ui| {
+        ui.add(
+            egui::Image::new(image_uri(dir, first))
+                .max_height(THUMBNAIL_HEIGHT)
+                .max_width(ui.available_width() - 40.0)
+                .rounding(4.0),
+        )
+        .on_hover_text(&first.name);
+        if more > 0 {
+            ui.label(egui::RichText::new(format!("📎+{}", more)).size(12.0).color(egui::Color32::from_rgb(150, 150, 155)));
+        }
+    });
+}
+
+impl ProductionManager {
+    pub(crate) fn attachments_dir(&self) -> PathBuf {
+        dir_for(&self.data_path)
+    }
+
+    /// Copies dropped or picked files into the attachments folder.
+    pub(crate) fn import_attachments(&mut self, paths: &[PathBuf]) -> Vec<Attachment> {
+        let dir = self.attachments_dir();
+        let mut imported = Vec::new();
+        for path in paths.iter().filter(|p| p.is_file()) {
+            match import(&dir, path) {
+                Ok(attachment) => imported.push(attachment),
+                Err(_) => self.show_status(&format!("{} {}", self.t(Msg::AttachFailed), path.display())),
+            }
+        }
+        imported
+    }
+
+    /// Attachment gallery for the edit popup.
+    pub(crate) fn render_attachment_gallery(&mut self, ui: &mut egui::Ui) {
+        let lang = self.settings.language;
+        let dir = self.attachments_dir();
+        ui.horizontal(|ui| {
+            ui.label(self.t(Msg::AttachmentsLabel));
+            if ui.add_enabled(!self.read_only, egui::Button::new(self.t(Msg::AttachFiles)).small()).clicked() {
+                if let Some(paths) = rfd::FileDialog::new().pick_files() {
+                    let imported = self.import_attachments(&paths);
+                    self.edit_item_attachments.extend(imported);
+                }
+            }
+            let paste = ui.add_enabled(!self.read_only, egui::Button::new(self.t(Msg::PasteImage)).small());
+            if paste.on_hover_text(self.t(Msg::PasteImageHint)).clicked() {
+                self.paste_into_edit();
+            }
+        });
+        if self.edit_item_attachments.is_empty() {
+            ui.label(egui::RichText::new(self.t(Msg::AttachmentsHint)).size(13.0).color(egui::Color32::from_rgb(150, 150, 155)));
+            return;
+        }
+
+        let mut remove = None;
+        ui.horizontal_wrapped(|ui| {
+            for (idx, attachment) in self.edit_item_attachments.iter().enumerate() {
+                ui.vertical(|ui| {
+                    ui.set_width(GALLERY_SIZE);
+                    let response = if attachment.is_image() {
+                        ui.add(
+                            egui::Image::new(image_uri(&dir, attachment))
+                                .fit_to_exact_size(egui::vec2(GALLERY_SIZE, GALLERY_SIZE))
+                                .maintain_aspect_ratio(true)
+                                .rounding(4.0)
+                                .sense(egui::Sense::click()),
+                        )
+                    } else {
+                        ui.add_sized([GALLERY_SIZE, GALLERY_SIZE], egui::Button::new(egui::RichText::new("📄").size(32.0)))
+                    };
+                    if response.on_hover_text(tr(lang, Msg::OpenAttachment)).clicked() {
+                        open_path(&dir.join(&attachment.file)).ok();
+                    }
+                    ui.horizontal(|ui| {
+                        ui.add(egui::Label::new(egui::RichText::new(&attachment.name).size(11.0)).truncate());
+                        let delete = ui.add_enabled(!self.read_only, egui::Button::new("✖").small());
+                        if delete.on_hover_text(tr(lang, Msg::Delete)).clicked() {
+                            remove = Some(idx);
+                        }
+                    });
+                });
+            }
+        });
+        if let Some(idx) = remove {
+            self.edit_item_attachments.remove(idx);
+        }
+    }
+
+    pub(crate) fn paste_into_edit(&mut self) {
+        match paste_image(&self.attachments_dir()) {
+            Some(attachment) => self.edit_item_attachments.push(attachment),
+            None => self.show_status(self.t(Msg::NoClipboardImage)),
+        }
+    }
+
+    /// Ctrl+V with an image on the clipboard while the edit popup is open
+    /// and no text field has focus. egui turns Ctrl+V into a text paste
+    /// event and drops the key press, so the key release is what we see.
+    pub(crate) fn handle_paste_shortcut(&mut self, ctx: &egui::Context) {
+        if !self.show_edit_popup || self.read_only || ctx.memory(|m| m.focused().is_some()) {
+            return;
+        }
+        let pasted = ctx.input(|i| {
+            i.events.iter().any(|e| {
+                matches!(e, egui::Event::Key { key: egui::Key::V, pressed: false, modifiers, .. } if modifiers.command)
+            })
+        });
+        if pasted {
+            self.paste_into_edit();
+        }
+    }
+
+    /// Attaches dropped files to the item with `item_id`.
+    pub(crate) fn attach_to_item(&mut self, item_id: &str, paths: &[PathBuf]) {
+        if self.read_only {
+            return;
+        }
+        let imported = self.import_attachments(paths);
+        if imported.is_empty() {
+            return;
+        }
+        let count = imported.len();
+        if let Some(item) = self.data.categories.iter_mut().flat_map(|c| &mut c.items).find(|i| i.id == item_id) {
+            item.attachments.extend(imported);
+            item.touch();
+        }
+        self.save_data();
+        self.show_status(&crate::i18n::fill(self.t(Msg::Attached), &[&count]));
+    }
+
+    /// Removes the files of attachments added in the edit popup that the
+    /// item doesn't keep: the ones removed when saving, or all new ones when
+    /// cancelling.
+    pub(crate) fn discard_attachments(&self, keep: &[Attachment], candidates: &[Attachment]) {
+        // The window holding the lock may still refer to them.
+        if self.read_only {
+            return;
+        }
+        let unused: Vec<Attachment> = candidates.iter().filter(|a| !keep.contains(a)).cloned().collect();
+        remove_files(&self.attachments_dir(), &unused);
+    }
+
+    /// Copies the attachments of `items` next to an exported Markdown file.
+    pub(crate) fn export_attachments<'a>(&self, items: impl Iterator<Item = &'a Item>, md_path: &Path) {
+        let from = self.attachments_dir();
+        let to = md_path.with_file_name(EXPORT_DIR);
+        for attachment in items.flat_map(|i| &i.attachments) {
+            if fs::create_dir_all(&to).is_ok() {
+                fs::copy(from.join(&attachment.file), to.join(&attachment.file)).ok();
+            }
+        }
+    }
+}
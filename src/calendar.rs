@@ -0,0 +1,272 @@
+//! Month and week calendar of creation dates, due dates and releases.
+//! Chips can be dragged to another day to change that date.
+
+use crate::i18n::Msg;
+use crate::status::today;
+use crate::{CardAction, ProductionManager};
+use chrono::{Datelike, Duration, Months, NaiveDate, NaiveDateTime};
+use eframe::egui;
+use serde::{Deserialize, Serialize};
+use std::collections::BTreeMap;
+use std::ops::RangeInclusive;
+
+/// Chips shown per day before the rest is summarized as "+N".
+const MAX_CHIPS_MONTH: usize = 4;
+const MAX_CHIPS_WEEK: usize = 20;
+
+#[derive(Serialize, Deserialize, Clone, Copy, Debug, PartialEq, Eq, Default)]
+pub enum CalendarMode {
+    #[default]
+    Month,
+    Week,
+}
+
+#[derive(Clone, Copy, Debug, PartialEq)]
+enum ChipKind {
+    Created,
+    Due,
+    /// Index into the item's releases.
+    Release(usize),
+}
+
+/// Drag payload: which date of which item a chip stands for.
+#[derive(Clone, Debug)]
+struct Chip {
+    cat_idx: usize,
+    item_id: String,
+    kind: ChipKind,
+    label: String,
+}
+
+fn week_start(date: NaiveDate) -> NaiveDate {
+    date - Duration::days(date.weekday().num_days_from_monday() as i64)
+}
+
+fn created_date(created_at: &str) -> Option<NaiveDate> {
+    NaiveDateTime::parse_from_str(created_at, "%Y-%m-%d %H:%M").ok().map(|d| d.date())
+}
+
+impl ProductionManager {
+    /// The chips of every day in `days`, grouped by day in one pass over
+    /// the items.
+    fn chips_by_day(&self, days: RangeInclusive<NaiveDate>) -> BTreeMap<NaiveDate, Vec<(Chip, egui::Color32)>> {
+        let mut chips: BTreeMap<NaiveDate, Vec<(Chip, egui::Color32)>> = BTreeMap::new();
+        for (cat_idx, cat) in self.data.categories.iter().enumerate() {
+            let color = cat.color(cat_idx);
+            for item in &cat.items {
+                let mut push = |day: Option<NaiveDate>, kind, label: &dyn Fn() -> String| {
+                    let Some(day) = day.filter(|d| days.contains(d)) else {
+                        return;
+                    };
+                    let chip = Chip {
+                        cat_idx,
+                        item_id: item.id.clone(),
+                        kind,
+                        label: label(),
+                    };
+                    chips.entry(day).or_default().push((chip, color));
+                };
+                push(item.due, ChipKind::Due, &|| format!("📅 {}", item.title));
+                for (idx, release) in item.releases.iter().enumerate() {
+                    push(crate::status::parse_date(&release.date), ChipKind::Release(idx), &|| format!("🏷 {} {}", item.title, release.version));
+                }
+                push(created_date(&item.created_at), ChipKind::Created, &|| format!("✚ {}", item.title));
+            }
+        }
+        chips
+    }
+
+    /// Moves the date a chip stands for to `day`.
+    fn move_chip(&mut self, chip: &Chip, day: NaiveDate) {
+        let Some(item) = self.data.categories.get_mut(chip.cat_idx).and_then(|c| c.items.iter_mut().find(|i| i.id == chip.item_id)) else {
+            return;
+        };
+        match chip.kind {
+            ChipKind::Created => {
+                // Keep the time of day.
+                let time = item.created_at.get(10..).unwrap_or(" 00:00").to_string();
+                item.created_at = format!("{}{}", day.format("%Y-%m-%d"), time);
+            }
+            ChipKind::Due => item.due = Some(day),
+            ChipKind::Release(idx) => {
+                if let Some(release) = item.releases.get_mut(idx) {
+                    release.date = day.format("%Y-%m-%d").to_string();
+                }
+                item.releases = crate::releases::normalized(&item.releases);
+            }
+        }
+        item.touch();
+        self.save_data();
+    }
+
+    fn open_chip(&mut self, chip: &Chip) {
+        let item_idx = self.data.categories[chip.cat_idx].items.iter().position(|i| i.id == chip.item_id);
+        if let Some(item_idx) = item_idx {
+            self.apply_card_action(chip.cat_idx, item_idx, CardAction::Edit);
+        }
+    }
+
+    fn render_calendar_toolbar(&mut self, ui: &mut egui::Ui, first: NaiveDate, last: NaiveDate) {
+        ui.horizontal(|ui| {
+            let mode = self.settings.calendar_mode;
+            let anchor = self.calendar_anchor;
+            let (prev, next) = match mode {
+                CalendarMode::Month => (
+                    anchor.checked_sub_months(Months::new(1)).unwrap_or(anchor),
+                    anchor.checked_add_months(Months::new(1)).unwrap_or(anchor),
+                ),
+                CalendarMode::Week => (anchor - Duration::weeks(1), anchor + Duration::weeks(1)),
+            };
+            if ui.button("◀").clicked() {
+                self.calendar_anchor = prev;
+            }
+            if ui.button(self.t(Msg::Today)).clicked() {
+                self.calendar_anchor = today();
+            }
+            if ui.button("▶").clicked() {
+                self.calendar_anchor = next;
+            }
+            ui.add_space(8.0);
+            let title = match mode {
+                CalendarMode::Month => anchor.format(self.t(Msg::CalendarMonthFormat)).to_string(),
+                CalendarMode::Week => format!("{} – {}", first.format("%Y-%m-%d"), last.format("%m-%d")),
+            };
+            ui.heading(title);
+
+            ui.with_layout(egui::Layout::right_to_left(egui::Align::Center), |ui| {
+                let mut selected = mode;
+                ui.selectable_value(&mut selected, CalendarMode::Week, self.t(Msg::CalendarWeek));
+                ui.selectable_value(&mut selected, CalendarMode::Month, self.t(Msg::CalendarMonth));
+                if selected != mode {
+                    self.settings.calendar_mode = selected;
+                    self.save_settings();
+                }
+                ui.add_space(12.0);
+                for (cat_idx, cat) in self.data.categories.iter().enumerate().rev() {
+                    ui.label(egui::RichText::new(&cat.name).size(12.0));
+                    ui.label(egui::RichText::new("■").color(cat.color(cat_idx)));
+                }
+            });
+        });
+    }
+
+    pub(crate) fn render_calendar(&mut self, ui: &mut egui::Ui) {
+        let mode = self.settings.calendar_mode;
+        let anchor = self.calendar_anchor;
+        let month_start = anchor.with_day(1).unwrap_or(anchor);
+        let first = match mode {
+            CalendarMode::Month => week_start(month_start),
+            CalendarMode::Week => week_start(anchor),
+        };
+        let rows = match mode {
+            CalendarMode::Month => {
+                let month_end = month_start.checked_add_months(Months::new(1)).unwrap_or(month_start) - Duration::days(1);
+                ((month_end - first).num_days() / 7 + 1) as usize
+            }
+            CalendarMode::Week => 1,
+        };
+        let last = first + Duration::days(rows as i64 * 7 - 1);
+
+        self.render_calendar_toolbar(ui, first, last);
+        ui.add_space(6.0);
+
+        let weekdays: Vec<&str> = self.t(Msg::Weekdays).split(',').collect();
+        let cell_width = ui.available_width() / 7.0;
+        ui.horizontal(|ui| {
+            ui.spacing_mut().item_spacing.x = 0.0;
+            for name in &weekdays {
+                ui.add_sized([cell_width, 18.0], egui::Label::new(egui::RichText::new(*name).size(13.0).color(egui::Color32::from_rgb(150, 150, 155))));
+            }
+        });
+
+        let cell_height = ((ui.available_height() - 4.0) / rows as f32).max(60.0);
+        let max_chips = match mode {
+            CalendarMode::Month => MAX_CHIPS_MONTH,
+            CalendarMode::Week => MAX_CHIPS_WEEK,
+        };
+        let today = today();
+        let origin = ui.cursor().min;
+        ui.allocate_space(egui::vec2(cell_width * 7.0, cell_height * rows as f32));
+
+        let mut open = None;
+        let mut dropped = None;
+        let mut add_on = None;
+        let chips_by_day = self.chips_by_day(first..=first + Duration::days((rows * 7 - 1) as i64));
+
+        for row in 0..rows {
+            for col in 0..7 {
+                let day = first + Duration::days((row * 7 + col) as i64);
+                let rect = egui::Rect::from_min_size(
+                    origin + egui::vec2(col as f32 * cell_width, row as f32 * cell_height),
+                    egui::vec2(cell_width, cell_height),
+                )
+                .shrink(2.0);
+                let response = ui.interact(rect, ui.id().with(("calendar_day", day)), egui::Sense::click());
+                let drop_hover = response.dnd_hover_payload::<Chip>().is_some();
+                if let Some(chip) = response.dnd_release_payload::<Chip>() {
+                    dropped = Some(((*chip).clone(), day));
+                }
+                if response.clicked() {
+                    add_on = Some(day);
+                }
+
+                let in_month = mode == CalendarMode::Week || day.month() == anchor.month();
+                let fill = if drop_hover {
+                    egui::Color32::from_rgb(50, 90, 50)
+                } else if in_month {
+                    egui::Color32::from_rgb(40, 40, 45)
+                } else {
+                    egui::Color32::from_rgb(32, 32, 36)
+                };
+                ui.painter().rect_filled(rect, 6.0, fill);
+                if day == today {
+                    ui.painter().rect_stroke(rect, 6.0, (1.5, egui::Color32::from_rgb(100, 140, 210)));
+                }
+                if response.hovered() && egui::DragAndDrop::payload::<Chip>(ui.ctx()).is_none() {
+                    response.clone().on_hover_text(self.t(Msg::CalendarAddHint));
+                }
+
+                let mut cell = ui.new_child(
+                    egui::UiBuilder::new()
+                        .max_rect(rect.shrink(4.0))
+                        .layout(egui::Layout::top_down(egui::Align::LEFT)),
+                );
+                cell.set_clip_rect(rect.intersect(ui.clip_rect()));
+                cell.spacing_mut().item_spacing.y = 2.0;
+                let day_color = if in_month { egui::Color32::from_rgb(200, 200, 205) } else { egui::Color32::from_rgb(110, 110, 115) };
+                cell.label(egui::RichText::new(day.day().to_string()).size(13.0).color(day_color));
+
+                let chips = chips_by_day.get(&day).map_or(&[][..], Vec::as_slice);
+                for (chip, color) in chips.iter().take(max_chips) {
+                    let button = egui::Button::new(egui::RichText::new(&chip.label).size(12.0).color(egui::Color32::WHITE))
+                        .fill(color.gamma_multiply(0.6))
+                        .stroke(egui::Stroke::NONE)
+                        .sense(egui::Sense::click_and_drag());
+                    let chip_response = cell.add_sized([cell.available_width(), 16.0], button).on_hover_text(&chip.label);
+                    chip_response.dnd_set_drag_payload(chip.clone());
+                    if chip_response.clicked() {
+                        open = Some(chip.clone());
+                    }
+                }
+                if chips.len() > max_chips {
+                    cell.label(egui::RichText::new(format!("+{}", chips.len() - max_chips)).size(12.0).color(egui::Color32::from_rgb(150, 150, 155)));
+                }
+            }
+        }
+
+        if let Some(chip) = egui::DragAndDrop::payload::<Chip>(ui.ctx()) {
+            ui.ctx().set_cursor_icon(egui::CursorIcon::Grabbing);
+            egui::show_tooltip_at_pointer(ui.ctx(), ui.layer_id(), egui::Id::new("calendar_drag"), |ui| {
+                ui.label(&chip.label);
+            });
+        }
+
+        if let Some((chip, day)) = dropped {
+            self.move_chip(&chip, day);
+        } else if let Some(chip) = open {
+            self.open_chip(&chip);
+        } else if let Some(day) = add_on {
+            self.open_add_popup(0, Some(day));
+        }
+    }
+}
@@ -0,0 +1,255 @@
+//! Statistics dashboard: counts by category and status, weekly activity,
+//! time to first release and overdue items. Charts are drawn with the
+//! painter so nothing beyond egui is needed.
+
+use crate::i18n::{fill, Msg};
+use crate::status::{parse_date, today, ItemStatus};
+use crate::{Item, ProductionManager};
+use chrono::{Datelike, Duration, NaiveDate, NaiveDateTime};
+use eframe::egui;
+
+/// How many weeks the activity charts go back, including the current one.
+const WEEKS: i64 = 12;
+const CHART_HEIGHT: f32 = 180.0;
+
+pub struct Bar {
+    pub label: String,
+    /// Stacked from the bottom: value, color and name for the tooltip.
+    pub segments: Vec<(f32, egui::Color32, String)>,
+}
+
+impl Bar {
+    fn single(label: String, value: f32, color: egui::Color32) -> Self {
+        Self {
+            label,
+            segments: vec![(value, color, String::new())],
+        }
+    }
+
+    fn total(&self) -> f32 {
+        self.segments.iter().map(|(v, _, _)| v).sum()
+    }
+}
+
+/// Round step for about four grid lines up to `max`.
+fn grid_step(max: f32) -> f32 {
+    let raw = (max / 4.0).max(1.0);
+    let magnitude = 10f32.powf(raw.log10().floor());
+    [1.0, 2.0, 5.0, 10.0]
+        .into_iter()
+        .map(|m| m * magnitude)
+        .find(|step| *step >= raw)
+        .unwrap_or(raw)
+}
+
+/// Stacked bar chart with a value axis and hover tooltips.
+pub fn bar_chart(ui: &mut egui::Ui, bars: &[Bar], height: f32) {
+    let (rect, response) = ui.allocate_exact_size(egui::vec2(ui.available_width(), height), egui::Sense::hover());
+    let painter = ui.painter_at(rect);
+    let text_color = egui::Color32::from_rgb(150, 150, 155);
+    let grid_color = egui::Color32::from_rgb(60, 60, 66);
+    let font = egui::FontId::proportional(11.0);
+
+    let plot = egui::Rect::from_min_max(rect.min + egui::vec2(28.0, 4.0), rect.max - egui::vec2(0.0, 18.0));
+    let max = bars.iter().map(Bar::total).fold(0.0, f32::max).max(1.0);
+    let step = grid_step(max);
+    let top = (max / step).ceil() * step;
+    let y_of = |value: f32| plot.bottom() - value / top * plot.height();
+
+    let mut value = 0.0;
+    while value <= top + 0.01 {
+        let y = y_of(value);
+        painter.line_segment([egui::pos2(plot.left(), y), egui::pos2(plot.right(), y)], (1.0, grid_color));
+        painter.text(egui::pos2(plot.left() - 4.0, y), egui::Align2::RIGHT_CENTER, format!("{}", value), font.clone(), text_color);
+        value += step;
+    }
+
+    if bars.is_empty() {
+        return;
+    }
+    let slot = plot.width() / bars.len() as f32;
+    let bar_width = (slot * 0.7).min(40.0);
+    // Skip labels when they would overlap.
+    let label_every = ((48.0 / slot).ceil() as usize).max(1);
+    let hover = response.hover_pos();
+
+    for (idx, bar) in bars.iter().enumerate() {
+        let center = plot.left() + slot * (idx as f32 + 0.5);
+        let mut base = 0.0;
+        for (value, color, _) in &bar.segments {
+            let segment = egui::Rect::from_min_max(
+                egui::pos2(center - bar_width / 2.0, y_of(base + value)),
+                egui::pos2(center + bar_width / 2.0, y_of(base)),
+            );
+            painter.rect_filled(segment, 2.0, *color);
+            base += value;
+        }
+        if idx % label_every == 0 {
+            painter.text(egui::pos2(center, plot.bottom() + 3.0), egui::Align2::CENTER_TOP, &bar.label, font.clone(), text_color);
+        }
+
+        let column = egui::Rect::from_x_y_ranges(center - slot / 2.0..=center + slot / 2.0, plot.y_range());
+        if hover.is_some_and(|pos| column.contains(pos)) {
+            painter.rect_filled(column, 0.0, egui::Color32::from_white_alpha(8));
+            let mut text = format!("{}: {}", bar.label, bar.total());
+            for (value, _, name) in bar.segments.iter().filter(|(_, _, name)| !name.is_empty()) {
+                text.push_str(&format!("\n{}: {}", name, value));
+            }
+            response.clone().on_hover_text_at_pointer(text);
+        }
+    }
+}
+
+fn created_date(item: &Item) -> Option<NaiveDate> {
+    NaiveDateTime::parse_from_str(&item.created_at, "%Y-%m-%d %H:%M").ok().map(|d| d.date())
+}
+
+fn completed_date(item: &Item) -> Option<NaiveDate> {
+    let completed = item.completed_at.as_deref()?;
+    NaiveDateTime::parse_from_str(completed, "%Y-%m-%d %H:%M").ok().map(|d| d.date())
+}
+
+fn week_start(date: NaiveDate) -> NaiveDate {
+    date - Duration::days(date.weekday().num_days_from_monday() as i64)
+}
+
+/// One bar per week for the last `WEEKS` weeks, counting `date_of`.
+fn weekly_bars<'a>(items: impl Iterator<Item = &'a Item>, date_of: fn(&Item) -> Option<NaiveDate>, color: egui::Color32) -> Vec<Bar> {
+    let first = week_start(today()) - Duration::weeks(WEEKS - 1);
+    let mut counts = [0u32; WEEKS as usize];
+    for date in items.filter_map(date_of) {
+        let week = (week_start(date) - first).num_weeks();
+        if (0..WEEKS).contains(&week) {
+            counts[week as usize] += 1;
+        }
+    }
+    counts
+        .iter()
+        .enumerate()
+        .map(|(week, &count)| {
+            let start = first + Duration::weeks(week as i64);
+            Bar::single(start.format("%m/%d").to_string(), count as f32, color)
+        })
+        .collect()
+}
+
+/// Mean days from creation to the first release, over items with releases.
+fn average_days_to_release<'a>(items: impl Iterator<Item = &'a Item>) -> Option<f64> {
+    let days: Vec<i64> = items
+        .filter_map(|item| {
+            let created = created_date(item)?;
+            let first_release = item.releases.iter().filter_map(|r| parse_date(&r.date)).min()?;
+            Some((first_release - created).num_days()).filter(|d| *d >= 0)
+        })
+        .collect();
+    (!days.is_empty()).then(|| days.iter().sum::<i64>() as f64 / days.len() as f64)
+}
+
+fn stat_tile(ui: &mut egui::Ui, label: &str, value: String, color: egui::Color32) {
+    egui::Frame::default()
+        .fill(egui::Color32::from_rgb(40, 40, 45))
+        .rounding(8.0)
+        .inner_margin(egui::vec2(16.0, 10.0))
+        .show(ui, |ui| {
+            ui.vertical(|ui| {
+                ui.label(egui::RichText::new(label).size(13.0).color(egui::Color32::from_rgb(150, 150, 155)));
+                ui.label(egui::RichText::new(value).size(24.0).strong().color(color));
+            });
+        });
+}
+
+fn chart_panel(ui: &mut egui::Ui, title: &str, add_contents: impl FnOnce(&mut egui::Ui)) {
+    egui::Frame::default()
+        .fill(egui::Color32::from_rgb(40, 40, 45))
+        .rounding(10.0)
+        .inner_margin(12.0)
+        .show(ui, |ui| {
+            ui.set_width(ui.available_width());
+            ui.strong(title);
+            ui.add_space(6.0);
+            add_contents(ui);
+        });
+}
+
+impl ProductionManager {
+    pub(crate) fn render_dashboard(&mut self, ui: &mut egui::Ui) {
+        let lang = self.settings.language;
+        let categories = &self.data.categories;
+        let all_items = || categories.iter().flat_map(|c| c.items.iter());
+        let today = today();
+
+        let total = all_items().count();
+        let in_progress = all_items().filter(|i| i.status == ItemStatus::InProgress).count();
+        let done = all_items().filter(|i| i.status == ItemStatus::Done).count();
+        let overdue = all_items().filter(|i| i.is_overdue(today)).count();
+        let to_release = average_days_to_release(all_items());
+
+        let by_status: Vec<Bar> = categories
+            .iter()
+            .map(|cat| Bar {
+                label: cat.name.clone(),
+                segments: ItemStatus::ALL
+                    .iter()
+                    .map(|&status| {
+                        let count = cat.items.iter().filter(|i| i.status == status).count();
+                        (count as f32, status.color(), status.name(lang).to_string())
+                    })
+                    .collect(),
+            })
+            .collect();
+        let overdue_bars: Vec<Bar> = categories
+            .iter()
+            .map(|cat| {
+                let count = cat.items.iter().filter(|i| i.is_overdue(today)).count();
+                Bar::single(cat.name.clone(), count as f32, egui::Color32::from_rgb(200, 90, 90))
+            })
+            .collect();
+        let created = weekly_bars(all_items(), created_date, egui::Color32::from_rgb(100, 140, 210));
+        let completed = weekly_bars(all_items(), completed_date, ItemStatus::Done.color());
+
+        egui::ScrollArea::vertical().auto_shrink([false, false]).show(ui, |ui| {
+            ui.horizontal_wrapped(|ui| {
+                let white = egui::Color32::from_rgb(230, 230, 235);
+                stat_tile(ui, self.t(Msg::DashboardTotal), total.to_string(), white);
+                stat_tile(ui, ItemStatus::InProgress.name(lang), in_progress.to_string(), ItemStatus::InProgress.color());
+                stat_tile(ui, ItemStatus::Done.name(lang), done.to_string(), ItemStatus::Done.color());
+                let overdue_color = if overdue > 0 { egui::Color32::from_rgb(220, 100, 100) } else { white };
+                stat_tile(ui, self.t(Msg::DashboardOverdue), overdue.to_string(), overdue_color);
+                let days = match to_release {
+                    Some(days) => fill(self.t(Msg::DashboardDays), &[&format!("{:.1}", days)]),
+                    None => "—".to_string(),
+                };
+                stat_tile(ui, self.t(Msg::DashboardTimeToRelease), days, white);
+            });
+
+            ui.add_space(12.0);
+
+            ui.columns(2, |columns| {
+                chart_panel(&mut columns[0], self.t(Msg::DashboardByStatus), |ui| {
+                    bar_chart(ui, &by_status, CHART_HEIGHT);
+                    ui.horizontal(|ui| {
+                        for status in ItemStatus::ALL {
+                            ui.label(egui::RichText::new("■").color(status.color()));
+                            ui.label(egui::RichText::new(status.name(lang)).size(12.0));
+                            ui.add_space(6.0);
+                        }
+                    });
+                });
+                chart_panel(&mut columns[1], self.t(Msg::DashboardOverdueByCategory), |ui| {
+                    bar_chart(ui, &overdue_bars, CHART_HEIGHT);
+                });
+            });
+
+            ui.add_space(12.0);
+
+            ui.columns(2, |columns| {
+                chart_panel(&mut columns[0], self.t(Msg::DashboardCreatedPerWeek), |ui| {
+                    bar_chart(ui, &created, CHART_HEIGHT);
+                });
+                chart_panel(&mut columns[1], self.t(Msg::DashboardCompletedPerWeek), |ui| {
+                    bar_chart(ui, &completed, CHART_HEIGHT);
+                });
+            });
+        });
+    }
+}
@@ -0,0 +1,337 @@
+//! User-defined fields. Each category has its own schema; items keep their
+//! values as text keyed by field id.
+
+use crate::i18n::{fill, tr, Language, Msg};
+use crate::status::parse_date;
+use crate::{Item, ProductionManager};
+use eframe::egui;
+use serde::{Deserialize, Serialize};
+use std::collections::BTreeMap;
+
+/// Field values by `FieldDef::id`.
+pub type FieldValues = BTreeMap<String, String>;
+
+#[derive(Serialize, Deserialize, Clone, Copy, Debug, PartialEq, Eq, Default)]
+pub enum FieldKind {
+    #[default]
+    Text,
+    Number,
+    Date,
+    Select,
+    Url,
+    Boolean,
+}
+
+impl FieldKind {
+    const ALL: [FieldKind; 6] = [
+        FieldKind::Text,
+        FieldKind::Number,
+        FieldKind::Date,
+        FieldKind::Select,
+        FieldKind::Url,
+        FieldKind::Boolean,
+    ];
+
+    fn name(self, lang: Language) -> &'static str {
+        let msg = match self {
+            FieldKind::Text => Msg::FieldText,
+            FieldKind::Number => Msg::FieldNumber,
+            FieldKind::Date => Msg::FieldDate,
+            FieldKind::Select => Msg::FieldSelect,
+            FieldKind::Url => Msg::FieldUrl,
+            FieldKind::Boolean => Msg::FieldBoolean,
+        };
+        tr(lang, msg)
+    }
+}
+
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq)]
+pub struct FieldDef {
+    pub id: String,
+    pub name: String,
+    #[serde(default)]
+    pub kind: FieldKind,
+    /// Choices of a select field.
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub options: Vec<String>,
+}
+
+impl FieldDef {
+    fn new() -> Self {
+        Self {
+            id: uuid::Uuid::new_v4().to_string(),
+            name: String::new(),
+            kind: FieldKind::default(),
+            options: Vec::new(),
+        }
+    }
+
+    fn is_valid(&self, value: &str) -> bool {
+        let value = value.trim();
+        value.is_empty()
+            || match self.kind {
+                FieldKind::Number => value.parse::<f64>().is_ok(),
+                FieldKind::Date => parse_date(value).is_some(),
+                _ => true,
+            }
+    }
+
+    /// The value as shown on cards and in exports.
+    pub fn display(&self, lang: Language, value: &str) -> String {
+        match self.kind {
+            FieldKind::Boolean => tr(lang, Msg::FieldYes).to_string(),
+            _ => value.to_string(),
+        }
+    }
+}
+
+/// The fields of `defs` that have a value, in schema order.
+pub fn filled<'a>(defs: &'a [FieldDef], values: &'a FieldValues) -> impl Iterator<Item = (&'a FieldDef, &'a str)> {
+    defs.iter()
+        .filter_map(|def| values.get(&def.id).map(|v| (def, v.as_str())))
+        .filter(|(_, v)| !v.is_empty())
+}
+
+/// True when every value parses as its field's kind.
+pub fn all_valid(defs: &[FieldDef], values: &FieldValues) -> bool {
+    defs.iter().all(|def| values.get(&def.id).is_none_or(|v| def.is_valid(v)))
+}
+
+/// Trimmed values without blanks.
+pub fn normalized(values: &FieldValues) -> FieldValues {
+    values
+        .iter()
+        .map(|(id, v)| (id.clone(), v.trim().to_string()))
+        .filter(|(_, v)| !v.is_empty())
+        .collect()
+}
+
+pub fn to_markdown(defs: &[FieldDef], values: &FieldValues, lang: Language) -> String {
+    filled(defs, values)
+        .map(|(def, value)| format!("*{}: {}*  \n", def.name, def.display(lang, value)))
+        .collect()
+}
+
+/// Moves `*Name: value*` lines of an imported comment into the matching
+/// fields of `defs`.
+pub fn take_from_comment(defs: &[FieldDef], item: &mut Item) {
+    if defs.is_empty() {
+        return;
+    }
+    let mut kept = Vec::new();
+    for line in item.comment.lines() {
+        let meta = line.trim_end().strip_prefix('*').and_then(|m| m.strip_suffix('*'));
+        let field = meta.and_then(|m| m.split_once(':')).and_then(|(name, value)| {
+            let def = defs.iter().find(|d| d.name == name.trim())?;
+            let value = match def.kind {
+                FieldKind::Boolean => "true",
+                _ => value.trim(),
+            };
+            Some((def.id.clone(), value.to_string()))
+        });
+        match field {
+            Some((id, value)) => {
+                item.fields.insert(id, value);
+            }
+            None => kept.push(line),
+        }
+    }
+    item.comment = kept.join("\n").trim().to_string();
+}
+
+/// `Name: value` labels on a card.
+pub fn render_field_values(ui: &mut egui::Ui, lang: Language, defs: &[FieldDef], values: &FieldValues) {
+    if filled(defs, values).next().is_none() {
+        return;
+    }
+    ui.add_space(2.0);
+    ui.horizontal_wrapped(|ui| {
+        ui.spacing_mut().item_spacing.x = 10.0;
+        let gray = egui::Color32::from_rgb(150, 150, 155);
+        for (def, value) in filled(defs, values) {
+            match def.kind {
+                FieldKind::Url => {
+                    ui.hyperlink_to(egui::RichText::new(format!("🔗 {}", def.name)).size(12.0), value)
+                        .on_hover_text(value);
+                }
+                FieldKind::Boolean => {
+                    ui.label(egui::RichText::new(format!("✔ {}", def.name)).size(12.0).color(gray));
+                }
+                _ => {
+                    ui.label(egui::RichText::new(format!("{}: {}", def.name, def.display(lang, value))).size(12.0).color(gray));
+                }
+            }
+        }
+    });
+}
+
+/// Inputs for every field of `defs`, for the edit popup.
+pub fn render_field_editor(ui: &mut egui::Ui, lang: Language, defs: &[FieldDef], values: &mut FieldValues) {
+    egui::Grid::new("field_editor").num_columns(2).spacing([8.0, 4.0]).show(ui, |ui| {
+        for def in defs {
+            ui.label(&def.name);
+            let value = values.entry(def.id.clone()).or_default();
+            match def.kind {
+                FieldKind::Boolean => {
+                    let mut checked = !value.is_empty();
+                    if ui.checkbox(&mut checked, "").changed() {
+                        *value = if checked { "true".to_string() } else { String::new() };
+                    }
+                }
+                FieldKind::Select => {
+                    let selected = if value.is_empty() { tr(lang, Msg::TemplateNone) } else { value.as_str() };
+                    egui::ComboBox::from_id_salt(("field_select", &def.id))
+                        .selected_text(selected.to_string())
+                        .show_ui(ui, |ui| {
+                            ui.selectable_value(value, String::new(), tr(lang, Msg::TemplateNone));
+                            for option in &def.options {
+                                ui.selectable_value(value, option.clone(), option);
+                            }
+                        });
+                }
+                kind => {
+                    let hint = match kind {
+                        FieldKind::Date => "YYYY-MM-DD",
+                        FieldKind::Url => "https://",
+                        _ => "",
+                    };
+                    let valid = def.is_valid(value);
+                    let mut edit = egui::TextEdit::singleline(value).desired_width(220.0).hint_text(hint);
+                    if !valid {
+                        edit = edit.text_color(egui::Color32::from_rgb(230, 100, 100));
+                    }
+                    let response = ui.add(edit);
+                    if !valid {
+                        response.on_hover_text(tr(lang, Msg::FieldInvalid));
+                    }
+                }
+            }
+            ui.end_row();
+        }
+    });
+}
+
+/// A field being edited, with its options as text.
+struct FieldDraft {
+    def: FieldDef,
+    options: String,
+}
+
+impl FieldDraft {
+    fn new(def: FieldDef) -> Self {
+        Self { options: def.options.join(", "), def }
+    }
+
+    fn finish(self) -> FieldDef {
+        let options = match self.def.kind {
+            FieldKind::Select => crate::tags::parse_tags(&self.options),
+            _ => Vec::new(),
+        };
+        FieldDef {
+            name: self.def.name.trim().to_string(),
+            options,
+            ..self.def
+        }
+    }
+}
+
+/// Working copy of one category's schema.
+pub struct FieldDialog {
+    cat_idx: usize,
+    drafts: Vec<FieldDraft>,
+}
+
+impl ProductionManager {
+    pub(crate) fn open_field_dialog(&mut self, cat_idx: usize) {
+        self.field_dialog = Some(FieldDialog {
+            cat_idx,
+            drafts: self.data.categories[cat_idx].fields.iter().cloned().map(FieldDraft::new).collect(),
+        });
+    }
+
+    pub(crate) fn render_field_dialog(&mut self, ctx: &egui::Context) {
+        let Some(mut dialog) = self.field_dialog.take() else {
+            return;
+        };
+        let Some(cat) = self.data.categories.get(dialog.cat_idx) else {
+            return;
+        };
+        let lang = self.settings.language;
+        let mut keep_open = true;
+        let mut save = false;
+
+        egui::Window::new(fill(tr(lang, Msg::FieldsTitle), &[&cat.name]))
+            .id(egui::Id::new("field_dialog"))
+            .collapsible(false)
+            .resizable(false)
+            .anchor(egui::Align2::CENTER_CENTER, [0.0, 0.0])
+            .show(ctx, |ui| {
+                if dialog.drafts.is_empty() {
+                    ui.label(egui::RichText::new(tr(lang, Msg::FieldsEmpty)).color(egui::Color32::from_rgb(150, 150, 155)));
+                }
+                let mut remove = None;
+                let mut move_up = None;
+                egui::Grid::new("field_schema").num_columns(4).spacing([8.0, 4.0]).show(ui, |ui| {
+                    for (idx, draft) in dialog.drafts.iter_mut().enumerate() {
+                        ui.add(egui::TextEdit::singleline(&mut draft.def.name).desired_width(160.0).hint_text(tr(lang, Msg::FieldName)));
+                        egui::ComboBox::from_id_salt(("field_kind", idx))
+                            .selected_text(draft.def.kind.name(lang))
+                            .show_ui(ui, |ui| {
+                                for kind in FieldKind::ALL {
+                                    ui.selectable_value(&mut draft.def.kind, kind, kind.name(lang));
+                                }
+                            });
+                        ui.add_enabled(
+                            draft.def.kind == FieldKind::Select,
+                            egui::TextEdit::singleline(&mut draft.options).desired_width(200.0).hint_text(tr(lang, Msg::FieldOptionsHint)),
+                        );
+                        ui.horizontal(|ui| {
+                            if ui.add_enabled(idx > 0, egui::Button::new("⬆").small()).clicked() {
+                                move_up = Some(idx);
+                            }
+                            if ui.small_button("✖").on_hover_text(tr(lang, Msg::Delete)).clicked() {
+                                remove = Some(idx);
+                            }
+                        });
+                        ui.end_row();
+                    }
+                });
+                if let Some(idx) = move_up {
+                    dialog.drafts.swap(idx - 1, idx);
+                }
+                if let Some(idx) = remove {
+                    dialog.drafts.remove(idx);
+                }
+                ui.add_space(6.0);
+                if ui.small_button(tr(lang, Msg::FieldAdd)).clicked() {
+                    dialog.drafts.push(FieldDraft::new(FieldDef::new()));
+                }
+
+                ui.add_space(12.0);
+                ui.horizontal(|ui| {
+                    if ui.button(tr(lang, Msg::Cancel)).clicked() {
+                        keep_open = false;
+                    }
+                    ui.add_space(20.0);
+                    let valid = dialog.drafts.iter().all(|d| !d.def.name.trim().is_empty());
+                    if ui.add_enabled(valid, egui::Button::new(tr(lang, Msg::Save))).clicked() {
+                        save = true;
+                    }
+                });
+            });
+
+        if save {
+            let cat = &mut self.data.categories[dialog.cat_idx];
+            cat.fields = dialog.drafts.into_iter().map(FieldDraft::finish).collect();
+            // Values of removed fields go with them.
+            let ids: Vec<&str> = cat.fields.iter().map(|f| f.id.as_str()).collect();
+            for item in &mut cat.items {
+                item.fields.retain(|id, _| ids.contains(&id.as_str()));
+            }
+            self.save_data();
+            self.show_status(self.t(Msg::Saved));
+        } else if keep_open {
+            self.field_dialog = Some(dialog);
+        }
+    }
+}
@@ -0,0 +1,176 @@
+//! Background status of the local git checkouts linked from items.
+
+use crate::i18n::{fill, tr, Language, Msg};
+use eframe::egui;
+use std::collections::{BTreeSet, HashMap};
+use std::path::Path;
+use std::process::Command;
+use std::sync::mpsc::{self, Receiver, RecvTimeoutError, Sender};
+use std::time::Duration;
+
+/// How often every repository is checked again.
+const REFRESH_INTERVAL: Duration = Duration::from_secs(60);
+
+#[derive(Clone, Debug, Default, PartialEq)]
+pub struct GitStatus {
+    pub branch: String,
+    /// Number of modified, staged or untracked paths.
+    pub changes: usize,
+    pub ahead: usize,
+    pub behind: usize,
+    /// Unix time and subject of the last commit.
+    pub last_commit: Option<(i64, String)>,
+}
+
+impl GitStatus {
+    pub fn days_since_commit(&self) -> Option<i64> {
+        self.last_commit
+            .as_ref()
+            .map(|(time, _)| (chrono::Utc::now().timestamp() - time) / 86_400)
+    }
+
+    pub fn is_stale(&self, stale_days: u32) -> bool {
+        stale_days > 0 && self.days_since_commit().is_some_and(|days| days >= stale_days as i64)
+    }
+}
+
+fn git(repo: &str, args: &[&str]) -> Option<String> {
+    let mut command = Command::new("git");
+    command.arg("-C").arg(repo).args(args);
+    #[cfg(windows)]
+    {
+        use std::os::windows::process::CommandExt;
+        const CREATE_NO_WINDOW: u32 = 0x0800_0000;
+        command.creation_flags(CREATE_NO_WINDOW);
+    }
+    let output = command.output().ok()?;
+    output
+        .status
+        .success()
+        .then(|| String::from_utf8_lossy(&output.stdout).into_owned())
+}
+
+/// Reads the status of the checkout at `repo`; `None` if it isn't one.
+fn read_status(repo: &str) -> Option<GitStatus> {
+    if !Path::new(repo).is_dir() {
+        return None;
+    }
+    let porcelain = git(repo, &["status", "--porcelain=v2", "--branch"])?;
+    let mut status = GitStatus::default();
+    for line in porcelain.lines() {
+        if let Some(head) = line.strip_prefix("# branch.head ") {
+            status.branch = head.to_string();
+        } else if let Some(ab) = line.strip_prefix("# branch.ab ") {
+            for part in ab.split_whitespace() {
+                if let Some(n) = part.strip_prefix('+') {
+                    status.ahead = n.parse().unwrap_or(0);
+                } else if let Some(n) = part.strip_prefix('-') {
+                    status.behind = n.parse().unwrap_or(0);
+                }
+            }
+        } else if !line.starts_with('#') && !line.is_empty() {
+            status.changes += 1;
+        }
+    }
+
+    status.last_commit = git(repo, &["log", "-1", "--format=%ct%x1f%s"]).and_then(|log| {
+        let (time, subject) = log.trim_end().split_once('\u{1f}')?;
+        Some((time.parse().ok()?, subject.to_string()))
+    });
+    Some(status)
+}
+
+/// Owns the refresh thread. Results arrive through `poll`.
+pub struct GitWatcher {
+    paths: Sender<BTreeSet<String>>,
+    results: Receiver<(String, Option<GitStatus>)>,
+    sent: BTreeSet<String>,
+}
+
+impl GitWatcher {
+    pub fn new(ctx: &egui::Context) -> Self {
+        let (paths_tx, paths_rx) = mpsc::channel::<BTreeSet<String>>();
+        let (results_tx, results_rx) = mpsc::channel();
+        let ctx = ctx.clone();
+
+        std::thread::Builder::new()
+            .name("git-status".to_string())
+            .spawn(move || {
+                let mut watched = BTreeSet::new();
+                loop {
+                    let to_check: Vec<String> = match paths_rx.recv_timeout(REFRESH_INTERVAL) {
+                        Ok(paths) => {
+                            // New paths right away; the rest on the next round.
+                            let added = paths.difference(&watched).cloned().collect();
+                            watched = paths;
+                            added
+                        }
+                        Err(RecvTimeoutError::Timeout) => watched.iter().cloned().collect(),
+                        Err(RecvTimeoutError::Disconnected) => break,
+                    };
+                    for repo in to_check {
+                        let status = read_status(&repo);
+                        if results_tx.send((repo, status)).is_err() {
+                            return;
+                        }
+                    }
+                    ctx.request_repaint();
+                }
+            })
+            .ok();
+
+        Self {
+            paths: paths_tx,
+            results: results_rx,
+            sent: BTreeSet::new(),
+        }
+    }
+
+    /// Tells the thread which checkouts to watch, if that changed.
+    pub fn watch(&mut self, paths: BTreeSet<String>) {
+        if paths != self.sent {
+            self.sent = paths.clone();
+            self.paths.send(paths).ok();
+        }
+    }
+
+    /// Moves finished results into `statuses`.
+    pub fn poll(&self, statuses: &mut HashMap<String, GitStatus>) {
+        while let Ok((repo, status)) = self.results.try_recv() {
+            match status {
+                Some(status) => statuses.insert(repo, status),
+                None => statuses.remove(&repo),
+            };
+        }
+    }
+}
+
+/// Status line shown on a card.
+pub fn render_git_status(ui: &mut egui::Ui, lang: Language, status: &GitStatus, stale_days: u32) {
+    let muted = egui::Color32::from_rgb(150, 150, 155);
+    ui.horizontal_wrapped(|ui| {
+        ui.spacing_mut().item_spacing.x = 6.0;
+        ui.label(egui::RichText::new(format!("🌿 {}", status.branch)).size(13.0).color(muted));
+        if status.changes > 0 {
+            ui.label(egui::RichText::new(format!("✎{}", status.changes)).size(13.0).color(egui::Color32::from_rgb(220, 180, 90)))
+                .on_hover_text(fill(tr(lang, Msg::GitUncommitted), &[&status.changes]));
+        }
+        if status.ahead > 0 || status.behind > 0 {
+            ui.label(egui::RichText::new(format!("↑{} ↓{}", status.ahead, status.behind)).size(13.0).color(muted))
+                .on_hover_text(fill(tr(lang, Msg::GitAheadBehind), &[&status.ahead, &status.behind]));
+        }
+        if status.is_stale(stale_days) {
+            let days = status.days_since_commit().unwrap_or_default();
+            ui.label(egui::RichText::new(fill(tr(lang, Msg::GitStale), &[&days])).size(13.0).color(egui::Color32::from_rgb(200, 120, 100)));
+        }
+    });
+    if let Some((time, subject)) = &status.last_commit {
+        let date = chrono::DateTime::from_timestamp(*time, 0)
+            .map(|d| d.with_timezone(&chrono::Local).format("%Y-%m-%d").to_string())
+            .unwrap_or_default();
+        ui.add(
+            egui::Label::new(egui::RichText::new(format!("{}  {}", date, subject)).size(13.0).color(muted)).truncate(),
+        )
+        .on_hover_text(subject);
+    }
+}
@@ -0,0 +1,665 @@
+//! Message catalog for the UI (Japanese / English).
+
+use serde::{Deserialize, Serialize};
+
+#[derive(Serialize, Deserialize, Clone, Copy, Debug, PartialEq, Eq, Default)]
+pub enum Language {
+    #[default]
+    Japanese,
+    English,
+}
+
+impl Language {
+    pub const ALL: [Language; 2] = [Language::Japanese, Language::English];
+
+    /// Name shown in the language selector, always in the language itself.
+    pub fn native_name(self) -> &'static str {
+        match self {
+            Language::Japanese => "日本語",
+            Language::English => "English",
+        }
+    }
+}
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum Msg {
+    // Default category names
+    CategoryExtensions,
+    CategoryWebApps,
+    CategoryWindowsApps,
+
+    // Column
+    Add,
+    ItemsCount,
+    SortManual,
+    SortTitleAsc,
+    SortTitleDesc,
+    SortCreated,
+    SortUpdated,
+    SortDue,
+    SortHint,
+    ExportMarkdownHint,
+    Edit,
+    Delete,
+
+    // Popups
+    NewItem,
+    EditItem,
+    CategoryLabel,
+    TitleLabel,
+    ReadingLabel,
+    ReadingHint,
+    CommentLabel,
+    Cancel,
+    Save,
+
+    // Status messages
+    Added,
+    Saved,
+    Deleted,
+    Exported,
+
+    // Settings
+    Settings,
+    LanguageLabel,
+    Close,
+
+    // Workspaces
+    DefaultWorkspaceName,
+    RecentWorkspaces,
+    AllWorkspaces,
+    NewWorkspace,
+    DuplicateWorkspace,
+    DeleteWorkspace,
+    WorkspaceNameLabel,
+    WorkspaceExists,
+    CopyOfWorkspace,
+    DeleteWorkspaceConfirm,
+    CannotDeleteLastWorkspace,
+    WorkspaceOpened,
+    WorkspaceDeleted,
+    Create,
+    DataOverrideHint,
+
+    // External changes
+    ReloadedExternalChanges,
+    MergeTitle,
+    MergeExplanation,
+    MergeAutoMerged,
+    MergeKeepLocal,
+    MergeTakeRemote,
+    MergeDeleted,
+    MergeTemplates,
+    MergeCategorySettings,
+    MergeCategoryList,
+    MergeUseAllLocal,
+    MergeUseAllRemote,
+    MergeApply,
+    MergeDone,
+
+    // Single-instance lock
+    AlreadyOpenTitle,
+    AlreadyOpenExplanation,
+    LockHeldBy,
+    ShowExistingWindow,
+    OpenReadOnly,
+    ReadOnlyBanner,
+    ResumeEditing,
+    StillLocked,
+    LockLost,
+    LockUnavailable,
+    WorkspaceInUse,
+
+    // Storage
+    StorageLabel,
+    MigrateToSqlite,
+    MigrateToSqliteHint,
+    MigratedToSqlite,
+    MigrationTargetExists,
+    SaveFailed,
+    SaveSaving,
+    SaveSaved,
+    SaveError,
+
+    // Links
+    LinksLabel,
+    LinkRepository,
+    LinkHomepage,
+    LinkStore,
+    LinkLocalFolder,
+
+    // Git status
+    GitUncommitted,
+    GitAheadBehind,
+    GitStale,
+    StaleDaysLabel,
+    StaleDaysUnit,
+
+    // Project scan
+    ScanFolder,
+    ScanTitle,
+    Scanning,
+    ScanFound,
+    ScanAddSelected,
+    ScanImported,
+
+    // Releases
+    ReleasesLabel,
+    AddRelease,
+    ReleaseVersion,
+    ReleaseNotes,
+    CurrentVersion,
+    CurrentVersionSince,
+    ExportChangelog,
+    ExportChangelogHint,
+
+    // Time tracking
+    StartTimer,
+    StopTimer,
+    TimeEntriesLabel,
+    TimeNote,
+    AddTimeEntry,
+    TimeReportTitle,
+    TimeReportRange,
+    ThisWeek,
+    ThisMonth,
+    TimeTotal,
+    InvalidDateRange,
+    ExportReportHint,
+    CsvDate,
+    CsvCategory,
+    CsvItem,
+    CsvStart,
+    CsvEnd,
+    CsvMinutes,
+
+    // Status and due date
+    StatusLabel,
+    StatusTodo,
+    StatusInProgress,
+    StatusDone,
+    DueDate,
+    DueDateLabel,
+    ScheduleLabel,
+
+    // Views
+    ViewBoard,
+    ViewCalendar,
+    ViewTimeline,
+    ViewDashboard,
+    ViewTable,
+    ViewGraph,
+
+    // Table
+    TableColumns,
+    TableHint,
+    TableRenameHint,
+    ColumnTitle,
+    ColumnStatus,
+    ColumnTags,
+    ColumnSchedule,
+    ColumnComment,
+    TagsLabel,
+    TagsHint,
+
+    // Relations
+    RelationsLabel,
+    RelationBlocks,
+    RelationBlockedBy,
+    RelationRelated,
+    RelationIncoming,
+    AddRelation,
+    Blocked,
+    BlockedByHint,
+    BlockedWarningTitle,
+    BlockedWarning,
+    MarkDoneAnyway,
+    GraphHint,
+    GraphEmpty,
+
+    // Attachments
+    AttachmentsLabel,
+    AttachmentsHint,
+    AttachFiles,
+    PasteImage,
+    PasteImageHint,
+    NoClipboardImage,
+    OpenAttachment,
+    AttachFailed,
+    Attached,
+    DropImported,
+
+    // Templates
+    TemplatesTitle,
+    TemplatesEmpty,
+    TemplateLabel,
+    TemplateNone,
+    TemplateName,
+    TemplateAdd,
+    TemplateNewName,
+    TemplateChecklistHint,
+    TemplateDefaults,
+    ChecklistLabel,
+    ChecklistAddHint,
+
+    // Custom fields
+    FieldsTitle,
+    FieldsEmpty,
+    FieldsLabel,
+    FieldName,
+    FieldAdd,
+    FieldOptionsHint,
+    FieldInvalid,
+    FieldYes,
+    FieldText,
+    FieldNumber,
+    FieldDate,
+    FieldSelect,
+    FieldUrl,
+    FieldBoolean,
+
+    // Search
+    SearchHint,
+    SearchClear,
+
+    // Recurrence
+    RecurrenceLabel,
+    RecurrenceHint,
+    RecurNone,
+    RecurDaily,
+    RecurWeekly,
+    RecurMonthly,
+    RecurYearly,
+    RecurCustom,
+    RecurEvery,
+    RecurEveryDays,
+    RecurEveryWeeks,
+    RecurEveryMonths,
+    RecurEveryYears,
+    RecurUnitDays,
+    RecurUnitWeeks,
+    RecurUnitMonths,
+    RecurUnitYears,
+    NextOccurrenceCreated,
+
+    // Reminders
+    RemindersTitle,
+    ReminderDueToday,
+    ReminderWhileAway,
+    RemindersDismiss,
+    RemindersSinceLastOpen,
+
+    // Calendar
+    CategoryColor,
+    Today,
+    CalendarMonth,
+    CalendarWeek,
+    CalendarMonthFormat,
+    Weekdays,
+    CalendarAddHint,
+
+    // Timeline
+    TimelineWeek,
+    TimelineMonth,
+    TimelineQuarter,
+    TimelineHint,
+    ExportSvg,
+
+    // Dashboard
+    DashboardTotal,
+    DashboardOverdue,
+    DashboardTimeToRelease,
+    DashboardDays,
+    DashboardByStatus,
+    DashboardOverdueByCategory,
+    DashboardCreatedPerWeek,
+    DashboardCompletedPerWeek,
+
+    // Markdown export
+    MarkdownCreated,
+    MarkdownReleases,
+    MarkdownTimeSpent,
+    MarkdownTags,
+}
+
+/// Looks up the translation of `msg`.
+pub fn tr(lang: Language, msg: Msg) -> &'static str {
+    let (ja, en) = match msg {
+        Msg::CategoryExtensions => ("拡張機能", "Extensions"),
+        Msg::CategoryWebApps => ("Webアプリ", "Web Apps"),
+        Msg::CategoryWindowsApps => ("Windowsアプリ", "Windows Apps"),
+
+        Msg::Add => ("追加", "Add"),
+        Msg::ItemsCount => ("{} 件", "{} items"),
+        Msg::SortManual => ("手動", "Manual"),
+        Msg::SortTitleAsc => ("タイトル昇順", "Title A-Z"),
+        Msg::SortTitleDesc => ("タイトル降順", "Title Z-A"),
+        Msg::SortCreated => ("作成日（新しい順）", "Newest created"),
+        Msg::SortUpdated => ("更新日（新しい順）", "Recently updated"),
+        Msg::SortDue => ("期限が近い順", "Due date"),
+        Msg::SortHint => (
+            "このカテゴリの並び順（手動の並びは保持されます）",
+            "Order of this category (the manual order is kept)",
+        ),
+        Msg::ExportMarkdownHint => ("Markdownでエクスポート", "Export as Markdown"),
+        Msg::Edit => ("編集", "Edit"),
+        Msg::Delete => ("削除", "Delete"),
+
+        Msg::NewItem => ("新規追加", "New Item"),
+        Msg::EditItem => ("編集", "Edit Item"),
+        Msg::CategoryLabel => ("カテゴリ:", "Category:"),
+        Msg::TitleLabel => ("タイトル:", "Title:"),
+        Msg::ReadingLabel => ("読み:", "Reading:"),
+        Msg::ReadingHint => ("並べ替え用（任意）", "Used for sorting (optional)"),
+        Msg::CommentLabel => ("コメント:", "Comment:"),
+        Msg::Cancel => ("キャンセル", "Cancel"),
+        Msg::Save => ("保存", "Save"),
+
+        Msg::Added => ("追加しました", "Added"),
+        Msg::Saved => ("保存しました", "Saved"),
+        Msg::Deleted => ("削除しました", "Deleted"),
+        Msg::Exported => ("エクスポートしました", "Exported!"),
+
+        Msg::Settings => ("設定", "Settings"),
+        Msg::LanguageLabel => ("言語:", "Language:"),
+        Msg::Close => ("閉じる", "Close"),
+
+        Msg::DefaultWorkspaceName => ("メイン", "Main"),
+        Msg::RecentWorkspaces => ("最近使ったワークスペース", "Recent workspaces"),
+        Msg::AllWorkspaces => ("すべてのワークスペース", "All workspaces"),
+        Msg::NewWorkspace => ("新しいワークスペース", "New workspace"),
+        Msg::DuplicateWorkspace => ("ワークスペースを複製", "Duplicate workspace"),
+        Msg::DeleteWorkspace => ("ワークスペースを削除", "Delete workspace"),
+        Msg::WorkspaceNameLabel => ("名前:", "Name:"),
+        Msg::WorkspaceExists => ("同じ名前のワークスペースがあります", "A workspace with this name already exists"),
+        Msg::CopyOfWorkspace => ("{} のコピー", "Copy of {}"),
+        Msg::DeleteWorkspaceConfirm => (
+            "ワークスペース「{}」とそのデータファイルを削除しますか？",
+            "Delete workspace \"{}\" and its data file?",
+        ),
+        Msg::CannotDeleteLastWorkspace => ("最後のワークスペースは削除できません", "The last workspace can't be deleted"),
+        Msg::WorkspaceOpened => ("「{}」を開きました", "Opened \"{}\""),
+        Msg::WorkspaceDeleted => ("ワークスペースを削除しました", "Workspace deleted"),
+        Msg::Create => ("作成", "Create"),
+        Msg::DataOverrideHint => (
+            "--data または PRODUCTION_MANAGER_DATA で指定されたファイルを使用中です",
+            "Using the file given by --data or PRODUCTION_MANAGER_DATA",
+        ),
+
+        Msg::ReloadedExternalChanges => ("外部の変更を読み込みました", "Reloaded changes made outside the app"),
+        Msg::MergeTitle => ("変更の競合", "Conflicting changes"),
+        Msg::MergeExplanation => (
+            "データファイルがアプリの外で変更されました。両方で変更された項目や設定ごとに、残す内容を選んでください。",
+            "The data file was changed outside the app. For each item or setting changed on both sides, choose which version to keep.",
+        ),
+        Msg::MergeAutoMerged => ("片方だけの変更 {} 件は自動で取り込みます", "{} one-sided changes will be merged automatically"),
+        Msg::MergeKeepLocal => ("このアプリ", "This app"),
+        Msg::MergeTakeRemote => ("ファイル", "File"),
+        Msg::MergeDeleted => ("（削除）", "(deleted)"),
+        Msg::MergeTemplates => ("テンプレート", "Templates"),
+        Msg::MergeCategorySettings => ("カテゴリ「{}」の設定", "Settings of category \"{}\""),
+        Msg::MergeCategoryList => ("カテゴリ: {}", "Categories: {}"),
+        Msg::MergeUseAllLocal => ("すべてこのアプリの内容", "Keep all of this app's"),
+        Msg::MergeUseAllRemote => ("すべてファイルの内容", "Take all from file"),
+        Msg::MergeApply => ("マージして保存", "Merge and save"),
+        Msg::MergeDone => ("マージしました", "Merged"),
+
+        Msg::AlreadyOpenTitle => ("すでに開かれています", "Already open"),
+        Msg::AlreadyOpenExplanation => (
+            "このデータファイルは別のウィンドウで編集中です。同時に書き込むと変更が失われるため、既存のウィンドウを使うか読み取り専用で開いてください。",
+            "This data file is being edited in another window. Writing from both would lose changes, so switch to that window or open this one read-only.",
+        ),
+        Msg::LockHeldBy => ("使用中: {} (PID {}、{} から)", "In use by {} (PID {}, since {})"),
+        Msg::ShowExistingWindow => ("既存のウィンドウを表示", "Show existing window"),
+        Msg::OpenReadOnly => ("読み取り専用で開く", "Open read-only"),
+        Msg::ReadOnlyBanner => ("🔒 読み取り専用（変更は保存されません）", "🔒 Read-only (changes are not saved)"),
+        Msg::ResumeEditing => ("編集を再開", "Resume editing"),
+        Msg::StillLocked => ("まだ別のウィンドウで開かれています", "Still open in another window"),
+        Msg::LockLost => (
+            "別のウィンドウがこのファイルを引き継いだため、読み取り専用になりました",
+            "Another window took over this file; now read-only",
+        ),
+        Msg::LockUnavailable => ("データファイルをロックできないため、読み取り専用で開きました", "Couldn't lock the data file; opened read-only"),
+        Msg::WorkspaceInUse => ("別のウィンドウで使用中のため削除できません", "Can't delete: open in another window"),
+
+        Msg::StorageLabel => ("保存形式:", "Storage:"),
+        Msg::MigrateToSqlite => ("SQLiteに移行", "Migrate to SQLite"),
+        Msg::MigrateToSqliteHint => (
+            "項目が多いボード向け。変更した項目だけを書き込みます。元のJSONファイルはバックアップとして残ります。",
+            "For large boards: only changed items are written. The JSON file is kept as a backup.",
+        ),
+        Msg::MigratedToSqlite => ("SQLiteに移行しました", "Migrated to SQLite"),
+        Msg::MigrationTargetExists => ("{} が既に存在します", "{} already exists"),
+        Msg::SaveFailed => ("保存に失敗しました: {}", "Save failed: {}"),
+        Msg::SaveSaving => ("保存中…", "Saving…"),
+        Msg::SaveSaved => ("✔ 保存済み", "✔ Saved"),
+        Msg::SaveError => ("⚠ 保存エラー", "⚠ Save error"),
+
+        Msg::LinksLabel => ("リンク:", "Links:"),
+        Msg::LinkRepository => ("リポジトリ", "Repository"),
+        Msg::LinkHomepage => ("ホームページ", "Homepage"),
+        Msg::LinkStore => ("ストア", "Store"),
+        Msg::LinkLocalFolder => ("ローカルフォルダ", "Local folder"),
+
+        Msg::GitUncommitted => ("未コミットの変更 {} 件", "{} uncommitted changes"),
+        Msg::GitAheadBehind => ("リモートより {} 進み / {} 遅れ", "{} ahead / {} behind upstream"),
+        Msg::GitStale => ("💤 {}日間コミットなし", "💤 No commits in {} days"),
+        Msg::StaleDaysLabel => ("停滞とみなす期間:", "Mark stale after:"),
+        Msg::StaleDaysUnit => ("日間コミットなし（0で無効）", "days without commits (0 = off)"),
+
+        Msg::ScanFolder => ("フォルダをスキャンしてプロジェクトを取り込む", "Scan a folder for projects to import"),
+        Msg::ScanTitle => ("プロジェクトの取り込み", "Import projects"),
+        Msg::Scanning => ("スキャン中…", "Scanning…"),
+        Msg::ScanFound => (
+            "{} 件のプロジェクトが見つかりました（登録済み {} 件は除外）",
+            "Found {} projects ({} already on the board were skipped)",
+        ),
+        Msg::ScanAddSelected => ("選択した {} 件を追加", "Add {} selected"),
+        Msg::ScanImported => ("{} 件のプロジェクトを追加しました", "Added {} projects"),
+
+        Msg::ReleasesLabel => ("リリース:", "Releases:"),
+        Msg::AddRelease => ("➕ リリースを追加", "➕ Add release"),
+        Msg::ReleaseVersion => ("バージョン", "Version"),
+        Msg::ReleaseNotes => ("変更内容（1行ごとに箇条書き）", "Changes (one bullet per line)"),
+        Msg::CurrentVersion => ("現在のバージョン", "Current version"),
+        Msg::CurrentVersionSince => ("現在のバージョン（{} リリース）", "Current version (released {})"),
+        Msg::ExportChangelog => ("📄 CHANGELOG", "📄 CHANGELOG"),
+        Msg::ExportChangelogHint => (
+            "Keep a Changelog 形式で CHANGELOG.md を書き出す",
+            "Export a CHANGELOG.md in Keep a Changelog format",
+        ),
+
+        Msg::StartTimer => ("タイマーを開始", "Start timer"),
+        Msg::StopTimer => ("タイマーを停止", "Stop timer"),
+        Msg::TimeEntriesLabel => ("作業時間:", "Time tracked:"),
+        Msg::TimeNote => ("メモ", "Note"),
+        Msg::AddTimeEntry => ("作業時間を手動で追加", "Add a time entry manually"),
+        Msg::TimeReportTitle => ("作業時間レポート", "Time report"),
+        Msg::TimeReportRange => ("期間:", "Range:"),
+        Msg::ThisWeek => ("今週", "This week"),
+        Msg::ThisMonth => ("今月", "This month"),
+        Msg::TimeTotal => ("合計", "Total"),
+        Msg::InvalidDateRange => ("日付は YYYY-MM-DD 形式で、開始日 ≤ 終了日にしてください", "Use YYYY-MM-DD dates with start ≤ end"),
+        Msg::ExportReportHint => ("この期間のレポートを書き出す", "Export the report for this range"),
+        Msg::CsvDate => ("日付", "Date"),
+        Msg::CsvCategory => ("カテゴリ", "Category"),
+        Msg::CsvItem => ("アイテム", "Item"),
+        Msg::CsvStart => ("開始", "Start"),
+        Msg::CsvEnd => ("終了", "End"),
+        Msg::CsvMinutes => ("分", "Minutes"),
+
+        Msg::StatusLabel => ("状態:", "Status:"),
+        Msg::StatusTodo => ("未着手", "To do"),
+        Msg::StatusInProgress => ("進行中", "In progress"),
+        Msg::StatusDone => ("完了", "Done"),
+        Msg::DueDate => ("期限", "Due date"),
+        Msg::DueDateLabel => ("期限:", "Due:"),
+        Msg::ScheduleLabel => ("予定期間:", "Planned:"),
+
+        Msg::ViewBoard => ("📋 ボード", "📋 Board"),
+        Msg::ViewCalendar => ("🗓 カレンダー", "🗓 Calendar"),
+        Msg::ViewTimeline => ("📈 タイムライン", "📈 Timeline"),
+        Msg::ViewDashboard => ("📊 ダッシュボード", "📊 Dashboard"),
+        Msg::ViewTable => ("📑 表", "📑 Table"),
+        Msg::ViewGraph => ("🔗 依存関係", "🔗 Dependencies"),
+
+        Msg::TableColumns => ("表示する列", "Columns"),
+        Msg::TableHint => ("見出しをクリックで並べ替え", "Click a heading to sort"),
+        Msg::TableRenameHint => ("クリックでタイトルを編集", "Click to rename"),
+        Msg::ColumnTitle => ("タイトル", "Title"),
+        Msg::ColumnStatus => ("状態", "Status"),
+        Msg::ColumnTags => ("タグ", "Tags"),
+        Msg::ColumnSchedule => ("予定期間", "Planned"),
+        Msg::ColumnComment => ("コメント", "Comment"),
+        Msg::TagsLabel => ("タグ:", "Tags:"),
+        Msg::TagsHint => ("カンマ区切り", "comma separated"),
+
+        Msg::RelationsLabel => ("関連アイテム:", "Relations:"),
+        Msg::RelationBlocks => ("ブロックする", "Blocks"),
+        Msg::RelationBlockedBy => ("ブロックされる", "Blocked by"),
+        Msg::RelationRelated => ("関連", "Related to"),
+        Msg::RelationIncoming => ("相手側で設定", "set on the other item"),
+        Msg::AddRelation => ("➕ 関連を追加", "➕ Add relation"),
+        Msg::Blocked => ("ブロック中", "Blocked"),
+        Msg::BlockedByHint => ("未完了の前提アイテム:", "Waiting on:"),
+        Msg::BlockedWarningTitle => ("⛔ 未完了の前提があります", "⛔ Open blockers"),
+        Msg::BlockedWarning => (
+            "「{}」を完了にしようとしていますが、次のアイテムがまだ完了していません:",
+            "\"{}\" still has open blockers:",
+        ),
+        Msg::MarkDoneAnyway => ("このまま完了にする", "Mark done anyway"),
+        Msg::GraphHint => (
+            "左のアイテムが右のアイテムをブロック、破線は関連。クリックで編集",
+            "Items on the left block those on the right; dashed lines are related. Click to edit",
+        ),
+        Msg::GraphEmpty => (
+            "関連のあるアイテムはありません。編集画面の「関連アイテム」から追加できます",
+            "No related items yet. Add relations from the edit dialog",
+        ),
+
+        Msg::AttachmentsLabel => ("添付ファイル:", "Attachments:"),
+        Msg::AttachmentsHint => (
+            "ファイルをこの画面にドロップ、または Ctrl+V で画像を貼り付け",
+            "Drop files here or press Ctrl+V to paste an image",
+        ),
+        Msg::AttachFiles => ("📎 ファイルを追加", "📎 Add files"),
+        Msg::PasteImage => ("📋 画像を貼り付け", "📋 Paste image"),
+        Msg::PasteImageHint => ("クリップボードの画像を添付", "Attach the image on the clipboard"),
+        Msg::NoClipboardImage => ("クリップボードに画像がありません", "No image on the clipboard"),
+        Msg::OpenAttachment => ("クリックで開く", "Click to open"),
+        Msg::AttachFailed => ("添付できませんでした:", "Could not attach:"),
+        Msg::Attached => ("{} 件のファイルを添付しました", "Attached {} file(s)"),
+        Msg::DropImported => ("{} 件のアイテムを「{}」に追加しました", "Added {} item(s) to {}"),
+
+        Msg::TemplatesTitle => ("📝 テンプレート", "📝 Templates"),
+        Msg::TemplatesEmpty => ("「➕」でテンプレートを追加", "Add a template with ➕"),
+        Msg::TemplateLabel => ("テンプレート:", "Template:"),
+        Msg::TemplateNone => ("なし", "None"),
+        Msg::TemplateName => ("名前:", "Name:"),
+        Msg::TemplateAdd => ("➕ 追加", "➕ Add"),
+        Msg::TemplateNewName => ("新しいテンプレート", "New template"),
+        Msg::TemplateChecklistHint => ("1行に1項目", "One entry per line"),
+        Msg::TemplateDefaults => ("カテゴリの既定テンプレート:", "Default template per category:"),
+        Msg::ChecklistLabel => ("チェックリスト:", "Checklist:"),
+        Msg::ChecklistAddHint => ("項目を追加", "Add an entry"),
+
+        Msg::FieldsTitle => ("🧩 {} のカスタム項目", "🧩 Custom fields of {}"),
+        Msg::FieldsEmpty => ("カスタム項目はまだありません", "No custom fields yet"),
+        Msg::FieldsLabel => ("カスタム項目:", "Custom fields:"),
+        Msg::FieldName => ("項目名", "Field name"),
+        Msg::FieldAdd => ("➕ 項目を追加", "➕ Add field"),
+        Msg::FieldOptionsHint => ("選択肢（カンマ区切り）", "Choices, comma separated"),
+        Msg::FieldInvalid => ("この種類の値として読み取れません", "Not a valid value for this field"),
+        Msg::FieldYes => ("はい", "Yes"),
+        Msg::FieldText => ("テキスト", "Text"),
+        Msg::FieldNumber => ("数値", "Number"),
+        Msg::FieldDate => ("日付", "Date"),
+        Msg::FieldSelect => ("選択", "Select"),
+        Msg::FieldUrl => ("URL", "URL"),
+        Msg::FieldBoolean => ("はい/いいえ", "Yes/No"),
+
+        Msg::SearchHint => ("🔍 検索", "🔍 Search"),
+        Msg::SearchClear => ("検索をクリア", "Clear search"),
+
+        Msg::RecurrenceLabel => ("繰り返し:", "Repeat:"),
+        Msg::RecurrenceHint => ("完了にすると次回分が作成されます", "Completing it creates the next occurrence"),
+        Msg::RecurNone => ("なし", "None"),
+        Msg::RecurDaily => ("毎日", "Daily"),
+        Msg::RecurWeekly => ("毎週", "Weekly"),
+        Msg::RecurMonthly => ("毎月", "Monthly"),
+        Msg::RecurYearly => ("毎年", "Yearly"),
+        Msg::RecurCustom => ("カスタム", "Custom"),
+        Msg::RecurEvery => ("間隔:", "Every"),
+        Msg::RecurEveryDays => ("{} 日ごと", "Every {} days"),
+        Msg::RecurEveryWeeks => ("{} 週間ごと", "Every {} weeks"),
+        Msg::RecurEveryMonths => ("{} か月ごと", "Every {} months"),
+        Msg::RecurEveryYears => ("{} 年ごと", "Every {} years"),
+        Msg::RecurUnitDays => ("日", "days"),
+        Msg::RecurUnitWeeks => ("週間", "weeks"),
+        Msg::RecurUnitMonths => ("か月", "months"),
+        Msg::RecurUnitYears => ("年", "years"),
+        Msg::NextOccurrenceCreated => ("次回分を作成しました（期限 {}）", "Created the next occurrence (due {})"),
+
+        Msg::RemindersTitle => ("🔔 期限を迎えたアイテム", "🔔 Due items"),
+        Msg::ReminderDueToday => ("今日", "Today"),
+        Msg::ReminderWhileAway => ("（閉じている間に期限）", "(came due while closed)"),
+        Msg::RemindersDismiss => ("すべて既読にする", "Dismiss all"),
+        Msg::RemindersSinceLastOpen => ("前回の起動以降に {} 件が期限を迎えました", "{} item(s) came due since the app was last opened"),
+
+        Msg::CategoryColor => ("カテゴリの色", "Category color"),
+        Msg::Today => ("今日", "Today"),
+        Msg::CalendarMonth => ("月", "Month"),
+        Msg::CalendarWeek => ("週", "Week"),
+        // chrono format strings
+        Msg::CalendarMonthFormat => ("%Y年%-m月", "%B %Y"),
+        Msg::Weekdays => ("月,火,水,木,金,土,日", "Mon,Tue,Wed,Thu,Fri,Sat,Sun"),
+        Msg::CalendarAddHint => (
+            "クリックでこの日を期限にしたアイテムを追加。チップはドラッグで日付を変更",
+            "Click to add an item due this day. Drag chips to change their date",
+        ),
+
+        Msg::TimelineWeek => ("週", "Week"),
+        Msg::TimelineMonth => ("月", "Month"),
+        Msg::TimelineQuarter => ("四半期", "Quarter"),
+        Msg::TimelineHint => (
+            "バーをドラッグで移動、端をドラッグで期間を変更。空の行をクリックすると予定を追加",
+            "Drag bars to move them and their edges to resize. Click an empty row to schedule",
+        ),
+        Msg::ExportSvg => ("🖼 SVGで書き出す", "🖼 Export SVG"),
+
+        Msg::DashboardTotal => ("アイテム数", "Items"),
+        Msg::DashboardOverdue => ("期限切れ", "Overdue"),
+        Msg::DashboardTimeToRelease => ("作成から初回リリースまで（平均）", "Creation to first release (avg)"),
+        Msg::DashboardDays => ("{} 日", "{} days"),
+        Msg::DashboardByStatus => ("カテゴリ別・状態別のアイテム数", "Items by category and status"),
+        Msg::DashboardOverdueByCategory => ("カテゴリ別の期限切れ", "Overdue by category"),
+        Msg::DashboardCreatedPerWeek => ("週ごとの作成数", "Created per week"),
+        Msg::DashboardCompletedPerWeek => ("週ごとの完了数", "Completed per week"),
+
+        Msg::MarkdownCreated => ("作成日", "Created"),
+        Msg::MarkdownReleases => ("リリース履歴", "Release history"),
+        Msg::MarkdownTimeSpent => ("作業時間", "Time spent"),
+        Msg::MarkdownTags => ("タグ", "Tags"),
+    };
+    match lang {
+        Language::Japanese => ja,
+        Language::English => en,
+    }
+}
+
+/// Fills the `{}` placeholders of a catalog string in order.
+pub fn fill(template: &str, args: &[&dyn std::fmt::Display]) -> String {
+    let mut out = String::with_capacity(template.len());
+    let mut args = args.iter();
+    let mut rest = template;
+    while let Some(pos) = rest.find("{}") {
+        out.push_str(&rest[..pos]);
+        match args.next() {
+            Some(arg) => out.push_str(&arg.to_string()),
+            None => out.push_str("{}"),
+        }
+        rest = &rest[pos + 2..];
+    }
+    out.push_str(rest);
+    out
+}
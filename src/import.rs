@@ -0,0 +1,368 @@
+//! Items from files dropped onto a column: project folders, Markdown files
+//! (including this app's own export), plain text lists and internet
+//! shortcuts.
+
+use crate::i18n::{fill, tr, Language, Msg};
+use crate::links::ItemLinks;
+use crate::recurrence::Recurrence;
+use crate::releases::Release;
+use crate::status::{parse_date, ItemStatus};
+use crate::templates::ChecklistEntry;
+use crate::{fields, scanner, Item, ProductionManager};
+use eframe::egui;
+use std::fs;
+use std::path::{Path, PathBuf};
+
+enum DropKind {
+    Folder,
+    Markdown,
+    Text,
+    Url,
+}
+
+fn kind_of(path: &Path) -> Option<DropKind> {
+    if path.is_dir() {
+        return Some(DropKind::Folder);
+    }
+    let ext = path.extension()?.to_str()?.to_ascii_lowercase();
+    match ext.as_str() {
+        "md" | "markdown" => Some(DropKind::Markdown),
+        "txt" => Some(DropKind::Text),
+        "url" => Some(DropKind::Url),
+        _ => None,
+    }
+}
+
+/// Whether a dropped path becomes items rather than an attachment.
+pub fn is_importable(path: &Path) -> bool {
+    kind_of(path).is_some()
+}
+
+/// An item read from Markdown and the attachment files it links to,
+/// relative to the Markdown file.
+pub struct ParsedItem {
+    pub item: Item,
+    pub files: Vec<String>,
+}
+
+/// `rest` after one of the translations of `msg`, with a colon dropped.
+fn after_label(line: &str, msg: Msg) -> Option<&str> {
+    [Language::Japanese, Language::English]
+        .into_iter()
+        .find_map(|lang| line.strip_prefix(tr(lang, msg)))
+        .map(|rest| rest.trim_start_matches(':').trim())
+}
+
+/// The text of a list entry and, for tasks, whether it is checked, or `None`
+/// for lines that aren't list entries.
+fn list_entry(line: &str) -> Option<(&str, Option<bool>)> {
+    let line = line.trim_start();
+    let rest = ["- ", "* ", "+ "]
+        .into_iter()
+        .find_map(|marker| line.strip_prefix(marker))
+        .or_else(|| {
+            let digits = line.find(|c: char| !c.is_ascii_digit())?;
+            (digits > 0).then(|| line[digits..].strip_prefix(". ").or_else(|| line[digits..].strip_prefix(") ")))?
+        })?;
+    for (checkbox, done) in [("[ ] ", false), ("[x] ", true), ("[X] ", true)] {
+        if let Some(task) = rest.strip_prefix(checkbox) {
+            return Some((task.trim(), Some(done)));
+        }
+    }
+    Some((rest.trim(), None))
+}
+
+/// `[label](target)` → `(label, target)`.
+fn md_link(text: &str) -> Option<(&str, &str)> {
+    let (label, rest) = text.strip_prefix('[')?.split_once("](")?;
+    Some((label, rest.strip_suffix(')')?))
+}
+
+/// Release line of the export: `**v1.2.0** (2024-05-01): notes`.
+fn parse_release(text: &str) -> Option<Release> {
+    let (version, rest) = text.strip_prefix("**")?.split_once("**")?;
+    let version = match version.strip_prefix('v') {
+        Some(number) if number.starts_with(|c: char| c.is_ascii_digit()) => number,
+        _ => version,
+    };
+    let rest = rest.trim();
+    let (date, notes) = match rest.strip_prefix('(').and_then(|r| r.split_once(')')) {
+        Some((date, notes)) => (date.to_string(), notes),
+        None => (String::new(), rest),
+    };
+    Some(Release {
+        version: version.to_string(),
+        date,
+        notes: notes.trim_start_matches(':').trim().to_string(),
+    })
+}
+
+/// Applies an exported `*label value*` line. Returns false when the line
+/// isn't one of ours.
+fn apply_meta(item: &mut Item, meta: &str) -> bool {
+    if let Some(value) = after_label(meta, Msg::StatusLabel) {
+        let status = ItemStatus::ALL
+            .into_iter()
+            .find(|s| [Language::Japanese, Language::English].into_iter().any(|lang| s.name(lang) == value));
+        if let Some(status) = status {
+            item.set_status(status);
+        }
+        return true;
+    }
+    if let Some(value) = after_label(meta, Msg::DueDateLabel) {
+        item.due = parse_date(value);
+        return true;
+    }
+    if let Some(value) = after_label(meta, Msg::ScheduleLabel) {
+        if let Some((start, end)) = value.split_once('–') {
+            item.start_date = parse_date(start);
+            item.end_date = parse_date(end);
+        }
+        return true;
+    }
+    if let Some(value) = after_label(meta, Msg::RecurrenceLabel) {
+        item.recurrence = Recurrence::parse(value);
+        return true;
+    }
+    if let Some(value) = after_label(meta, Msg::MarkdownTags) {
+        item.tags = value.split_whitespace().map(|t| t.trim_start_matches('#').to_string()).filter(|t| !t.is_empty()).collect();
+        return true;
+    }
+    if let Some(value) = after_label(meta, Msg::MarkdownCreated) {
+        if chrono::NaiveDateTime::parse_from_str(value, "%Y-%m-%d %H:%M").is_ok() {
+            item.created_at = value.to_string();
+        }
+        return true;
+    }
+    after_label(meta, Msg::MarkdownTimeSpent).is_some()
+}
+
+/// Applies an exported link list entry. Returns false for other entries.
+fn apply_link(links: &mut ItemLinks, entry: &str) -> bool {
+    if let Some((label, target)) = md_link(entry) {
+        let field = if after_label(label, Msg::LinkRepository).is_some() {
+            &mut links.repository
+        } else if after_label(label, Msg::LinkHomepage).is_some() {
+            &mut links.homepage
+        } else if after_label(label, Msg::LinkStore).is_some() {
+            &mut links.store
+        } else {
+            return false;
+        };
+        *field = target.to_string();
+        return true;
+    }
+    match after_label(entry, Msg::LinkLocalFolder) {
+        Some(path) => {
+            links.local_path = path.trim_matches('`').to_string();
+            true
+        }
+        None => false,
+    }
+}
+
+/// Reads items from Markdown. `##` headings start items when there are any
+/// (the `#` heading is then the category, as in the export), otherwise `#`
+/// headings do; without headings every list entry is an item.
+pub fn parse_markdown(md: &str) -> Vec<ParsedItem> {
+    let lines: Vec<&str> = md.lines().collect();
+    let heading = if lines.iter().any(|l| l.starts_with("## ")) {
+        "## "
+    } else if lines.iter().any(|l| l.starts_with("# ")) {
+        "# "
+    } else {
+        return lines
+            .iter()
+            .filter_map(|l| list_entry(l))
+            .filter(|(title, _)| !title.is_empty())
+            .map(|(title, done)| {
+                let mut item = Item::new(title.to_string(), String::new(), 0);
+                if done == Some(true) {
+                    item.set_status(ItemStatus::Done);
+                }
+                ParsedItem { item, files: Vec::new() }
+            })
+            .collect();
+    };
+
+    let mut parsed: Vec<ParsedItem> = Vec::new();
+    let mut comment: Vec<&str> = Vec::new();
+    let mut in_releases = false;
+    let finish = |parsed: &mut Vec<ParsedItem>, comment: &mut Vec<&str>| {
+        if let Some(last) = parsed.last_mut() {
+            last.item.comment = comment.join("\n").trim().to_string();
+        }
+        comment.clear();
+    };
+
+    for line in lines {
+        if let Some(title) = line.strip_prefix(heading) {
+            finish(&mut parsed, &mut comment);
+            in_releases = false;
+            parsed.push(ParsedItem {
+                item: Item::new(title.trim().to_string(), String::new(), 0),
+                files: Vec::new(),
+            });
+            continue;
+        }
+        let Some(current) = parsed.last_mut() else {
+            continue;
+        };
+        let trimmed = line.trim_end();
+        if let Some(section) = trimmed.strip_prefix("### ") {
+            in_releases = after_label(section, Msg::MarkdownReleases).is_some();
+            if !in_releases {
+                comment.push(line);
+            }
+            continue;
+        }
+        if in_releases {
+            if let Some(release) = list_entry(trimmed).and_then(|(entry, _)| parse_release(entry)) {
+                current.item.releases.push(release);
+                continue;
+            }
+            if let (Some(notes), Some(release)) = (trimmed.strip_prefix("  "), current.item.releases.last_mut()) {
+                release.notes = format!("{}\n{}", release.notes, notes.trim()).trim().to_string();
+                continue;
+            }
+            if trimmed.is_empty() {
+                continue;
+            }
+            in_releases = false;
+        }
+        if trimmed == "---" {
+            continue;
+        }
+        if let Some(meta) = trimmed.strip_prefix('*').and_then(|m| m.strip_suffix('*')).filter(|m| !m.starts_with('*')) {
+            if apply_meta(&mut current.item, meta) {
+                continue;
+            }
+        }
+        if let Some((_, target)) = trimmed.strip_prefix('!').and_then(md_link) {
+            current.files.push(target.to_string());
+            continue;
+        }
+        if let Some((entry, task)) = list_entry(trimmed) {
+            if let Some(done) = task.filter(|_| !entry.is_empty()) {
+                current.item.checklist.push(ChecklistEntry { text: entry.to_string(), done });
+                continue;
+            }
+            if let Some((_, target)) = entry.strip_prefix("📎 ").and_then(md_link) {
+                current.files.push(target.to_string());
+                continue;
+            }
+            if apply_link(&mut current.item.links, entry) {
+                continue;
+            }
+        }
+        comment.push(line);
+    }
+    finish(&mut parsed, &mut comment);
+    parsed
+}
+
+/// One item per non-empty line; list markers and checkboxes are dropped.
+pub fn parse_text_list(text: &str) -> Vec<Item> {
+    text.lines()
+        .map(|line| list_entry(line).map_or(line.trim(), |(entry, _)| entry))
+        .filter(|title| !title.is_empty())
+        .map(|title| Item::new(title.to_string(), String::new(), 0))
+        .collect()
+}
+
+/// Item for a Windows internet shortcut (`[InternetShortcut]` / `URL=`).
+/// Code hosting sites go to the repository link, anything else to the
+/// homepage.
+pub fn parse_url_file(path: &Path, text: &str) -> Option<Item> {
+    let url = text.lines().find_map(|l| l.trim().strip_prefix("URL="))?.trim().to_string();
+    let title = path.file_stem()?.to_string_lossy().into_owned();
+    let mut item = Item::new(title, String::new(), 0);
+    let host = url.split("://").nth(1).unwrap_or(&url).to_ascii_lowercase();
+    if ["github.com", "gitlab.com", "bitbucket.org", "codeberg.org"].iter().any(|h| host.starts_with(h)) {
+        item.links.repository = url;
+    } else {
+        item.links.homepage = url;
+    }
+    Some(item)
+}
+
+impl ProductionManager {
+    /// Adds items for dropped folders and files to the column `cat_idx`.
+    fn import_dropped(&mut self, cat_idx: usize, paths: &[PathBuf]) {
+        let mut items: Vec<Item> = Vec::new();
+        for path in paths {
+            match kind_of(path) {
+                Some(DropKind::Folder) => items.push(scanner::item_for_folder(path, 0)),
+                Some(DropKind::Markdown) => {
+                    let Ok(md) = fs::read_to_string(path) else { continue };
+                    let base = path.parent().unwrap_or(Path::new(""));
+                    for ParsedItem { mut item, files } in parse_markdown(&md) {
+                        let sources: Vec<PathBuf> = files.iter().map(|f| base.join(f)).collect();
+                        item.attachments = self.import_attachments(&sources);
+                        items.push(item);
+                    }
+                }
+                Some(DropKind::Text) => {
+                    if let Ok(text) = fs::read_to_string(path) {
+                        items.extend(parse_text_list(&text));
+                    }
+                }
+                Some(DropKind::Url) => {
+                    if let Some(item) = fs::read_to_string(path).ok().and_then(|text| parse_url_file(path, &text)) {
+                        items.push(item);
+                    }
+                }
+                None => {}
+            }
+        }
+        let Some(cat) = self.data.categories.get_mut(cat_idx) else {
+            return;
+        };
+        let added = items.len();
+        let name = cat.name.clone();
+        for mut item in items {
+            fields::take_from_comment(&cat.fields, &mut item);
+            item.order = cat.items.len();
+            cat.items.push(item);
+        }
+        if added > 0 {
+            self.save_data();
+        }
+        self.show_status(&fill(self.t(Msg::DropImported), &[&added, &name]));
+    }
+
+    /// Files dropped on the window: everything goes to the edit popup when
+    /// it is open. Otherwise folders, Markdown, text lists and shortcuts
+    /// become items in the column under the pointer and other files are
+    /// attached to the card under it.
+    pub(crate) fn handle_dropped_files(&mut self, ctx: &egui::Context) {
+        if ctx.input(|i| i.raw.hovered_files.is_empty() && i.raw.dropped_files.is_empty()) {
+            self.file_drop_card = None;
+            self.file_drop_column = None;
+            return;
+        }
+        let paths: Vec<PathBuf> = ctx.input(|i| i.raw.dropped_files.iter().filter_map(|f| f.path.clone()).collect());
+        if paths.is_empty() || self.read_only {
+            return;
+        }
+        if self.show_edit_popup {
+            let imported = self.import_attachments(&paths);
+            self.edit_item_attachments.extend(imported);
+            return;
+        }
+        let (importable, files): (Vec<PathBuf>, Vec<PathBuf>) = paths.into_iter().partition(|p| is_importable(p));
+        if let (false, Some(cat_idx)) = (importable.is_empty(), self.file_drop_column) {
+            self.import_dropped(cat_idx, &importable);
+        }
+        if let (false, Some(item_id)) = (files.is_empty(), self.file_drop_card.clone()) {
+            self.attach_to_item(&item_id, &files);
+        }
+        self.file_drop_card = None;
+        self.file_drop_column = None;
+    }
+}
+
+/// Outline around the column that dropped folders and lists would go to.
+pub fn highlight_drop_column(ui: &egui::Ui, rect: egui::Rect) {
+    ui.painter().rect_stroke(rect, 10.0, egui::Stroke::new(2.0, egui::Color32::from_rgb(90, 170, 90)));
+}
@@ -0,0 +1,155 @@
+//! Structured project links on items: repository, homepage, store listing
+//! and local folder.
+
+use crate::i18n::{tr, Language, Msg};
+use eframe::egui;
+use serde::{Deserialize, Serialize};
+use std::path::Path;
+
+#[derive(Serialize, Deserialize, Clone, Debug, Default, PartialEq)]
+pub struct ItemLinks {
+    #[serde(default)]
+    pub repository: String,
+    #[serde(default)]
+    pub homepage: String,
+    /// Store listing (Chrome Web Store, Microsoft Store, itch.io, ...).
+    #[serde(default)]
+    pub store: String,
+    #[serde(default)]
+    pub local_path: String,
+}
+
+impl ItemLinks {
+    pub fn is_empty(&self) -> bool {
+        self.repository.is_empty() && self.homepage.is_empty() && self.store.is_empty() && self.local_path.is_empty()
+    }
+
+    pub fn trimmed(&self) -> Self {
+        Self {
+            repository: self.repository.trim().to_string(),
+            homepage: self.homepage.trim().to_string(),
+            store: self.store.trim().to_string(),
+            local_path: self.local_path.trim().to_string(),
+        }
+    }
+
+    /// `(icon, label, target, is_local_path)` for every link that is set.
+    fn entries(&self, lang: Language) -> Vec<(&'static str, String, &str, bool)> {
+        let mut entries = Vec::new();
+        if !self.repository.is_empty() {
+            entries.push(("🗃", tr(lang, Msg::LinkRepository).to_string(), self.repository.as_str(), false));
+        }
+        if !self.homepage.is_empty() {
+            entries.push(("🌐", tr(lang, Msg::LinkHomepage).to_string(), self.homepage.as_str(), false));
+        }
+        if !self.store.is_empty() {
+            let label = match store_name(&self.store) {
+                Some(store) => format!("{} ({})", tr(lang, Msg::LinkStore), store),
+                None => tr(lang, Msg::LinkStore).to_string(),
+            };
+            entries.push(("🛒", label, self.store.as_str(), false));
+        }
+        if !self.local_path.is_empty() {
+            entries.push(("📁", tr(lang, Msg::LinkLocalFolder).to_string(), self.local_path.as_str(), true));
+        }
+        entries
+    }
+
+    /// Markdown link list for exports.
+    pub fn to_markdown(&self, lang: Language) -> String {
+        let mut md = String::new();
+        for (_, label, target, is_local) in self.entries(lang) {
+            if is_local {
+                md.push_str(&format!("- {}: `{}`\n", label, target));
+            } else {
+                md.push_str(&format!("- [{}]({})\n", label, normalized_url(target)));
+            }
+        }
+        if !md.is_empty() {
+            md.push('\n');
+        }
+        md
+    }
+}
+
+/// Recognizes well-known store listing hosts.
+fn store_name(url: &str) -> Option<&'static str> {
+    let url = url.to_ascii_lowercase();
+    if url.contains("chromewebstore.google.com") || url.contains("chrome.google.com/webstore") {
+        Some("Chrome Web Store")
+    } else if url.contains("microsoftedge.microsoft.com") {
+        Some("Edge Add-ons")
+    } else if url.contains("addons.mozilla.org") {
+        Some("Firefox Add-ons")
+    } else if url.contains("apps.microsoft.com") || url.contains("microsoft.com/store") {
+        Some("Microsoft Store")
+    } else if url.contains("itch.io") {
+        Some("itch.io")
+    } else {
+        None
+    }
+}
+
+/// Adds `https://` when the scheme was left out.
+pub fn normalized_url(url: &str) -> String {
+    if url.contains("://") || url.starts_with("mailto:") {
+        url.to_string()
+    } else {
+        format!("https://{}", url)
+    }
+}
+
+/// Opens a file or folder with the OS handler.
+pub fn open_path(path: &Path) -> std::io::Result<()> {
+    #[cfg(windows)]
+    let mut command = std::process::Command::new("explorer");
+    #[cfg(target_os = "macos")]
+    let mut command = std::process::Command::new("open");
+    #[cfg(all(unix, not(target_os = "macos")))]
+    let mut command = std::process::Command::new("xdg-open");
+    command.arg(path).spawn().map(|_| ())
+}
+
+/// One small icon button per link; clicking opens it.
+pub fn render_link_icons(ui: &mut egui::Ui, lang: Language, links: &ItemLinks) {
+    for (icon, label, target, is_local) in links.entries(lang) {
+        let response = ui
+            .add(egui::Button::new(icon).frame(false))
+            .on_hover_text(format!("{}\n{}", label, target));
+        if response.clicked() {
+            if is_local {
+                open_path(Path::new(target)).ok();
+            } else {
+                ui.ctx().open_url(egui::OpenUrl::new_tab(normalized_url(target)));
+            }
+        }
+    }
+}
+
+/// Text fields for editing all links.
+pub fn render_link_editor(ui: &mut egui::Ui, lang: Language, links: &mut ItemLinks) {
+    egui::Grid::new("link_editor").num_columns(2).spacing([8.0, 4.0]).show(ui, |ui| {
+        ui.label(format!("🗃 {}", tr(lang, Msg::LinkRepository)));
+        ui.add_sized([230.0, 20.0], egui::TextEdit::singleline(&mut links.repository).hint_text("https://github.com/..."));
+        ui.end_row();
+
+        ui.label(format!("🌐 {}", tr(lang, Msg::LinkHomepage)));
+        ui.add_sized([230.0, 20.0], egui::TextEdit::singleline(&mut links.homepage).hint_text("https://..."));
+        ui.end_row();
+
+        ui.label(format!("🛒 {}", tr(lang, Msg::LinkStore)));
+        ui.add_sized([230.0, 20.0], egui::TextEdit::singleline(&mut links.store).hint_text("https://chromewebstore.google.com/..."));
+        ui.end_row();
+
+        ui.label(format!("📁 {}", tr(lang, Msg::LinkLocalFolder)));
+        ui.horizontal(|ui| {
+            ui.add_sized([200.0, 20.0], egui::TextEdit::singleline(&mut links.local_path));
+            if ui.small_button("…").clicked() {
+                if let Some(dir) = rfd::FileDialog::new().pick_folder() {
+                    links.local_path = dir.display().to_string();
+                }
+            }
+        });
+        ui.end_row();
+    });
+}
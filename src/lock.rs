@@ -0,0 +1,347 @@
+//! Advisory lock that keeps two windows from writing the same data file.
+
+use crate::i18n::{fill, Msg};
+use crate::ProductionManager;
+use eframe::egui;
+use serde::{Deserialize, Serialize};
+use std::fs::{self, OpenOptions};
+use std::path::{Path, PathBuf};
+use std::sync::atomic::{AtomicBool, Ordering};
+use std::sync::mpsc::{self, RecvTimeoutError, Sender};
+use std::sync::Arc;
+use std::thread::JoinHandle;
+use std::time::Duration;
+
+/// How often a running instance refreshes its lock file.
+const HEARTBEAT: Duration = Duration::from_secs(10);
+/// A lock whose heartbeat is older than this is considered abandoned.
+const STALE_AFTER_SECS: i64 = 120;
+
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq)]
+pub struct LockInfo {
+    pub pid: u32,
+    pub host: String,
+    pub since: String,
+    /// Unix time of the last refresh.
+    pub heartbeat: i64,
+}
+
+impl LockInfo {
+    fn current() -> Self {
+        Self {
+            pid: std::process::id(),
+            host: host_name(),
+            since: chrono::Local::now().format("%Y-%m-%d %H:%M").to_string(),
+            heartbeat: chrono::Utc::now().timestamp(),
+        }
+    }
+
+    fn is_ours(&self) -> bool {
+        self.pid == std::process::id() && self.host == host_name()
+    }
+
+    /// True if the owning process has gone away without removing the lock.
+    /// An old heartbeat is enough: after a crash the PID may belong to an
+    /// unrelated process by now.
+    fn is_stale(&self) -> bool {
+        if chrono::Utc::now().timestamp() - self.heartbeat > STALE_AFTER_SECS {
+            return true;
+        }
+        self.host == host_name() && process_alive(self.pid) == Some(false)
+    }
+}
+
+/// A held lock. The lock file is removed when this is dropped.
+pub struct DataLock {
+    path: PathBuf,
+    info: LockInfo,
+    /// Set by the heartbeat thread when another process took the lock over.
+    lost: Arc<AtomicBool>,
+    /// Dropping this stops the heartbeat thread.
+    stop: Option<Sender<()>>,
+    heartbeat: Option<JoinHandle<()>>,
+}
+
+impl DataLock {
+    /// Takes the lock for `data_path`, recovering stale locks. Returns the
+    /// current holder if another live process has it, or `None` when the
+    /// lock file can't be created at all.
+    pub fn acquire(data_path: &Path) -> Result<Self, Option<LockInfo>> {
+        let path = sibling(data_path, "lock");
+        if let Some(parent) = path.parent() {
+            fs::create_dir_all(parent).ok();
+        }
+        let info = LockInfo::current();
+
+        for _ in 0..3 {
+            match OpenOptions::new().write(true).create_new(true).open(&path) {
+                Ok(_) => {
+                    // The empty file claims the lock; the details replace it whole.
+                    write_lock(&path, &info);
+                    return Ok(Self::hold(path, info));
+                }
+                Err(_) => match read_lock(&path) {
+                    Some(holder) if !holder.is_ours() && !holder.is_stale() => return Err(Some(holder)),
+                    // Just created by another window that hasn't filled it in yet.
+                    None if modified_within(&path, STALE_AFTER_SECS) => std::thread::sleep(Duration::from_millis(50)),
+                    _ => {
+                        fs::remove_file(&path).ok();
+                    }
+                },
+            }
+        }
+        // Lost a race for the file, or it can't be created (e.g. a read-only
+        // folder). Either way this window doesn't own it.
+        Err(read_lock(&path).filter(|holder| !holder.is_ours()))
+    }
+
+    /// Starts refreshing the heartbeat of a freshly taken lock. This runs on
+    /// its own thread so that a minimized window, or one waiting in a modal
+    /// file dialog, doesn't look abandoned.
+    fn hold(path: PathBuf, info: LockInfo) -> Self {
+        let lost = Arc::new(AtomicBool::new(false));
+        let (stop_tx, stop_rx) = mpsc::channel::<()>();
+        let heartbeat = {
+            let path = path.clone();
+            let mut info = info.clone();
+            let lost = lost.clone();
+            std::thread::Builder::new()
+                .name("lock heartbeat".to_string())
+                .spawn(move || {
+                    while let Err(RecvTimeoutError::Timeout) = stop_rx.recv_timeout(HEARTBEAT) {
+                        if !refresh(&path, &mut info) {
+                            lost.store(true, Ordering::Relaxed);
+                            break;
+                        }
+                    }
+                })
+                .ok()
+        };
+        Self {
+            path,
+            info,
+            lost,
+            stop: Some(stop_tx),
+            heartbeat,
+        }
+    }
+
+    /// True once another process has taken the lock over.
+    pub fn is_lost(&self) -> bool {
+        self.lost.load(Ordering::Relaxed)
+    }
+
+    /// Asks the instance holding `data_path` to bring its window to the front.
+    pub fn request_focus(data_path: &Path) {
+        fs::write(sibling(data_path, "focus"), std::process::id().to_string()).ok();
+    }
+
+    /// Consumes a pending focus request for `data_path`, if any.
+    pub fn take_focus_request(data_path: &Path) -> bool {
+        fs::remove_file(sibling(data_path, "focus")).is_ok()
+    }
+}
+
+impl Drop for DataLock {
+    fn drop(&mut self) {
+        self.stop = None;
+        if let Some(heartbeat) = self.heartbeat.take() {
+            heartbeat.join().ok();
+        }
+        if read_lock(&self.path).is_some_and(|holder| holder.pid == self.info.pid && holder.host == self.info.host) {
+            fs::remove_file(&self.path).ok();
+        }
+    }
+}
+
+/// `data.json` -> `data.json.<ext>`
+fn sibling(data_path: &Path, ext: &str) -> PathBuf {
+    let mut name = data_path.file_name().unwrap_or_default().to_os_string();
+    name.push(".");
+    name.push(ext);
+    data_path.with_file_name(name)
+}
+
+/// Updates the heartbeat in the lock file. Returns false if another process
+/// took the lock over in the meantime.
+fn refresh(path: &Path, info: &mut LockInfo) -> bool {
+    if read_lock(path).is_some_and(|holder| holder.pid != info.pid || holder.host != info.host) {
+        return false;
+    }
+    info.heartbeat = chrono::Utc::now().timestamp();
+    write_lock(path, info);
+    true
+}
+
+fn read_lock(path: &Path) -> Option<LockInfo> {
+    fs::read_to_string(path)
+        .ok()
+        .and_then(|content| serde_json::from_str(&content).ok())
+}
+
+/// Writes a temporary file and renames it over `path`, so readers never see
+/// a half-written lock.
+fn write_lock(path: &Path, info: &LockInfo) {
+    let Ok(json) = serde_json::to_string_pretty(info) else {
+        return;
+    };
+    let mut temp = path.as_os_str().to_os_string();
+    temp.push(format!(".{}.tmp", info.pid));
+    let temp = PathBuf::from(temp);
+    if fs::write(&temp, json).is_err() || fs::rename(&temp, path).is_err() {
+        fs::remove_file(&temp).ok();
+    }
+}
+
+/// True if the file at `path` was modified less than `secs` ago, or if its
+/// age can't be told.
+fn modified_within(path: &Path, secs: i64) -> bool {
+    fs::metadata(path)
+        .and_then(|meta| meta.modified())
+        .ok()
+        .and_then(|modified| modified.elapsed().ok())
+        .is_none_or(|age| age.as_secs() < secs.unsigned_abs())
+}
+
+fn host_name() -> String {
+    std::env::var("COMPUTERNAME")
+        .or_else(|_| std::env::var("HOSTNAME"))
+        .ok()
+        .or_else(|| fs::read_to_string("/etc/hostname").ok())
+        .map(|name| name.trim().to_string())
+        .filter(|name| !name.is_empty())
+        .unwrap_or_else(|| "unknown".to_string())
+}
+
+/// Whether a process with this PID is running on this machine, if that can
+/// be determined.
+#[cfg(target_os = "linux")]
+fn process_alive(pid: u32) -> Option<bool> {
+    Some(Path::new(&format!("/proc/{}", pid)).exists())
+}
+
+#[cfg(windows)]
+fn process_alive(pid: u32) -> Option<bool> {
+    use std::os::windows::process::CommandExt;
+    const CREATE_NO_WINDOW: u32 = 0x0800_0000;
+    let output = std::process::Command::new("tasklist")
+        .args(["/FI", &format!("PID eq {}", pid), "/NH", "/FO", "CSV"])
+        .creation_flags(CREATE_NO_WINDOW)
+        .output()
+        .ok()?;
+    Some(String::from_utf8_lossy(&output.stdout).contains(&format!("\"{}\"", pid)))
+}
+
+#[cfg(all(unix, not(target_os = "linux")))]
+fn process_alive(pid: u32) -> Option<bool> {
+    std::process::Command::new("kill")
+        .args(["-0", &pid.to_string()])
+        .status()
+        .ok()
+        .map(|status| status.success())
+}
+
+#[cfg(not(any(unix, windows)))]
+fn process_alive(_pid: u32) -> Option<bool> {
+    None
+}
+
+impl ProductionManager {
+    /// Takes the lock for `data_path`; falls back to read-only mode when
+    /// another window already has the file open.
+    pub(crate) fn acquire_lock(&mut self) {
+        self.lock = None;
+        match DataLock::acquire(&self.data_path) {
+            Ok(lock) => {
+                self.lock = Some(lock);
+                self.read_only = false;
+                self.lock_holder = None;
+            }
+            Err(Some(holder)) => {
+                self.read_only = true;
+                self.lock_holder = Some(holder);
+                self.show_lock_prompt = true;
+            }
+            Err(None) => {
+                self.read_only = true;
+                self.lock_holder = None;
+                self.show_status(self.t(Msg::LockUnavailable));
+            }
+        }
+    }
+
+    /// Focus requests and lock takeovers; called from `update`.
+    pub(crate) fn poll_lock(&mut self, ctx: &egui::Context) {
+        if self.lock.is_some() && DataLock::take_focus_request(&self.data_path) {
+            ctx.send_viewport_cmd(egui::ViewportCommand::Minimized(false));
+            ctx.send_viewport_cmd(egui::ViewportCommand::Focus);
+            ctx.send_viewport_cmd(egui::ViewportCommand::RequestUserAttention(
+                egui::UserAttentionType::Informational,
+            ));
+        }
+
+        if let Some(lock) = self.lock.as_ref().filter(|lock| lock.is_lost()) {
+            self.lock_holder = read_lock(&lock.path);
+            self.lock = None;
+            self.read_only = true;
+            self.show_status(self.t(Msg::LockLost));
+        }
+    }
+
+    fn lock_holder_text(&self) -> String {
+        match &self.lock_holder {
+            Some(holder) => fill(self.t(Msg::LockHeldBy), &[&holder.host, &holder.pid, &holder.since]),
+            None => String::new(),
+        }
+    }
+
+    pub(crate) fn render_read_only_banner(&mut self, ui: &mut egui::Ui) {
+        if !self.read_only {
+            return;
+        }
+        if ui.button(self.t(Msg::ResumeEditing)).clicked() {
+            self.acquire_lock();
+            if self.read_only {
+                self.show_lock_prompt = false;
+                self.show_status(self.t(Msg::StillLocked));
+            } else {
+                self.reload_data();
+            }
+        }
+        ui.label(egui::RichText::new(self.t(Msg::ReadOnlyBanner)).color(egui::Color32::from_rgb(230, 180, 80)))
+            .on_hover_text(self.lock_holder_text());
+    }
+
+    pub(crate) fn render_lock_prompt(&mut self, ctx: &egui::Context) {
+        if !self.show_lock_prompt {
+            return;
+        }
+        egui::Window::new(self.t(Msg::AlreadyOpenTitle))
+            .id(egui::Id::new("lock_prompt"))
+            .collapsible(false)
+            .resizable(false)
+            .anchor(egui::Align2::CENTER_CENTER, [0.0, 0.0])
+            .show(ctx, |ui| {
+                ui.label(self.t(Msg::AlreadyOpenExplanation));
+                ui.label(egui::RichText::new(self.lock_holder_text()).color(egui::Color32::from_rgb(150, 150, 155)));
+
+                ui.add_space(12.0);
+
+                ui.horizontal(|ui| {
+                    if ui.button(self.t(Msg::ShowExistingWindow)).clicked() {
+                        DataLock::request_focus(&self.data_path);
+                        self.show_lock_prompt = false;
+                        if self.lock_prompt_closes_app {
+                            ctx.send_viewport_cmd(egui::ViewportCommand::Close);
+                        }
+                        self.lock_prompt_closes_app = false;
+                    }
+                    ui.add_space(20.0);
+                    if ui.button(self.t(Msg::OpenReadOnly)).clicked() {
+                        self.show_lock_prompt = false;
+                        self.lock_prompt_closes_app = false;
+                    }
+                });
+            });
+    }
+}
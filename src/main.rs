@@ -1,21 +1,127 @@
 #![cfg_attr(not(debug_assertions), windows_subsystem = "windows")]
 
+mod attachments;
+mod calendar;
+mod collate;
+mod dashboard;
+mod fields;
+mod gitstatus;
+mod i18n;
+mod import;
+mod links;
+mod lock;
+mod merge;
+mod recurrence;
+mod relations;
+mod releases;
+mod saver;
+mod scanner;
+mod search;
+mod sorting;
+mod status;
+mod storage;
+mod table;
+mod tags;
+mod templates;
+mod timeline;
+mod timetrack;
+mod workspace;
+
+use attachments::Attachment;
+use calendar::CalendarMode;
 use eframe::egui::{self, FontData, FontDefinitions, FontFamily};
+use fields::{FieldDef, FieldDialog, FieldValues};
+use gitstatus::{GitStatus, GitWatcher};
+use i18n::{fill, tr, Language, Msg};
+use links::ItemLinks;
+use lock::{DataLock, LockInfo};
+use merge::{FileStamp, MergeState};
+use recurrence::Recurrence;
+use relations::{PendingDone, Relation};
+use releases::Release;
+use saver::{SaveStatus, Saver};
+use scanner::ScanState;
+use search::SearchHits;
+use sorting::SortMode;
+use status::ItemStatus;
+use storage::Storage;
+use table::{TableColumn, TitleEdit};
+use templates::{ChecklistEntry, Template, TemplateDialog};
+use timeline::{TimelineDrag, TimelineZoom};
+use timetrack::{CategoryTime, TimeDraft, TimeEntry, TimeReport};
 use serde::{Deserialize, Serialize};
+use std::collections::{BTreeMap, BTreeSet, HashMap, HashSet};
 use std::fs;
 use std::path::PathBuf;
+use std::sync::Arc;
 use uuid::Uuid;
+use workspace::{Workspace, WorkspaceDialog};
 
 const APP_VERSION: &str = "0.19.0";
 const APP_NAME: &str = "Production Manager";
+/// Height assumed for cards that haven't been laid out yet.
+const ESTIMATED_CARD_HEIGHT: f32 = 80.0;
+const CARD_SPACING: f32 = 6.0;
+/// When this file sits next to the executable, all data is kept there too.
+const PORTABLE_MARKER: &str = "portable.txt";
+/// Environment variable that points the app at a specific data file.
+const DATA_ENV_VAR: &str = "PRODUCTION_MANAGER_DATA";
+/// Column colors for categories that haven't picked one.
+const CATEGORY_PALETTE: [[u8; 3]; 6] = [
+    [100, 140, 210],
+    [90, 170, 120],
+    [210, 140, 80],
+    [170, 110, 200],
+    [200, 100, 120],
+    [90, 170, 180],
+];
 
-#[derive(Serialize, Deserialize, Clone, Debug)]
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq)]
 struct Item {
     id: String,
     title: String,
+    /// Kana reading used instead of the title when sorting.
+    #[serde(default, skip_serializing_if = "String::is_empty")]
+    reading: String,
     comment: String,
     order: usize,
     created_at: String,
+    /// Last edit, `%Y-%m-%d %H:%M`. Missing until the item is first changed.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    updated_at: Option<String>,
+    #[serde(default, skip_serializing_if = "ItemLinks::is_empty")]
+    links: ItemLinks,
+    /// Newest first.
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    releases: Vec<Release>,
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    time_entries: Vec<TimeEntry>,
+    #[serde(default, skip_serializing_if = "ItemStatus::is_todo")]
+    status: ItemStatus,
+    /// When the item was last marked done, `%Y-%m-%d %H:%M`.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    completed_at: Option<String>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    due: Option<chrono::NaiveDate>,
+    /// Planned schedule shown on the timeline.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    start_date: Option<chrono::NaiveDate>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    end_date: Option<chrono::NaiveDate>,
+    /// Completing the item creates the next occurrence.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    recurrence: Option<Recurrence>,
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    tags: Vec<String>,
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    relations: Vec<Relation>,
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    attachments: Vec<Attachment>,
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    checklist: Vec<ChecklistEntry>,
+    /// Values of the category's custom fields.
+    #[serde(default, skip_serializing_if = "BTreeMap::is_empty")]
+    fields: FieldValues,
 }
 
 impl Item {
@@ -23,16 +129,89 @@ impl Item {
         Self {
             id: Uuid::new_v4().to_string(),
             title,
+            reading: String::new(),
             comment,
             order,
             created_at: chrono::Local::now().format("%Y-%m-%d %H:%M").to_string(),
+            updated_at: None,
+            links: ItemLinks::default(),
+            releases: Vec::new(),
+            time_entries: Vec::new(),
+            status: ItemStatus::default(),
+            completed_at: None,
+            due: None,
+            start_date: None,
+            end_date: None,
+            recurrence: None,
+            tags: Vec::new(),
+            relations: Vec::new(),
+            attachments: Vec::new(),
+            checklist: Vec::new(),
+            fields: FieldValues::new(),
         }
     }
+
+    /// Records an edit for the "updated" sort.
+    fn touch(&mut self) {
+        self.updated_at = Some(chrono::Local::now().format("%Y-%m-%d %H:%M").to_string());
+    }
+
+    /// What title sorts compare: the reading if there is one.
+    fn sort_name(&self) -> &str {
+        if self.reading.is_empty() { &self.title } else { &self.reading }
+    }
+
+    fn updated(&self) -> &str {
+        self.updated_at.as_deref().unwrap_or(&self.created_at)
+    }
 }
 
-#[derive(Serialize, Deserialize, Clone, Debug)]
+/// The categories created by `AppData::default()`. Their names come from the
+/// message catalog until the user renames them.
+#[derive(Serialize, Deserialize, Clone, Copy, Debug, PartialEq, Eq)]
+enum BuiltinCategory {
+    Extensions,
+    WebApps,
+    WindowsApps,
+}
+
+impl BuiltinCategory {
+    const ALL: [BuiltinCategory; 3] = [
+        BuiltinCategory::Extensions,
+        BuiltinCategory::WebApps,
+        BuiltinCategory::WindowsApps,
+    ];
+
+    fn name(self, lang: Language) -> &'static str {
+        let msg = match self {
+            BuiltinCategory::Extensions => Msg::CategoryExtensions,
+            BuiltinCategory::WebApps => Msg::CategoryWebApps,
+            BuiltinCategory::WindowsApps => Msg::CategoryWindowsApps,
+        };
+        tr(lang, msg)
+    }
+
+    /// True if `name` is this category's default name in any language.
+    fn is_default_name(self, name: &str) -> bool {
+        Language::ALL.iter().any(|&lang| self.name(lang) == name)
+    }
+}
+
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq)]
 struct Category {
     name: String,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    builtin: Option<BuiltinCategory>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    color: Option<[u8; 3]>,
+    #[serde(default, skip_serializing_if = "SortMode::is_manual")]
+    sort: SortMode,
+    /// `Template::id` preselected when adding to this category.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    default_template: Option<String>,
+    /// Custom fields of this category's items.
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    fields: Vec<FieldDef>,
     items: Vec<Item>,
 }
 
@@ -40,13 +219,46 @@ impl Category {
     fn new(name: &str) -> Self {
         Self {
             name: name.to_string(),
+            builtin: None,
+            color: None,
+            sort: SortMode::default(),
+            default_template: None,
+            fields: Vec::new(),
             items: Vec::new(),
         }
     }
 
-    fn add_item(&mut self, title: String, comment: String) {
+    fn new_builtin(builtin: BuiltinCategory, lang: Language) -> Self {
+        Self {
+            builtin: Some(builtin),
+            ..Self::new(builtin.name(lang))
+        }
+    }
+
+    /// Copy of everything except the items.
+    fn header(&self) -> Self {
+        let Category { name, builtin, color, sort, default_template, fields, items: _ } = self;
+        Self {
+            name: name.clone(),
+            builtin: *builtin,
+            color: *color,
+            sort: *sort,
+            default_template: default_template.clone(),
+            fields: fields.clone(),
+            items: Vec::new(),
+        }
+    }
+
+    /// The chosen color, or one from the palette by column position.
+    fn color(&self, cat_idx: usize) -> egui::Color32 {
+        let [r, g, b] = self.color.unwrap_or(CATEGORY_PALETTE[cat_idx % CATEGORY_PALETTE.len()]);
+        egui::Color32::from_rgb(r, g, b)
+    }
+
+    fn add_item(&mut self, title: String, comment: String) -> &mut Item {
         let order = self.items.len();
         self.items.push(Item::new(title, comment, order));
+        self.items.last_mut().unwrap()
     }
 
     fn remove_item(&mut self, id: &str) {
@@ -60,94 +272,453 @@ impl Category {
         }
     }
 
-    fn sort_by_title(&mut self) {
-        self.items.sort_by(|a, b| a.title.cmp(&b.title));
-        self.reorder_items();
-    }
-
-    fn sort_by_date(&mut self) {
-        self.items.sort_by(|a, b| b.created_at.cmp(&a.created_at));
-        self.reorder_items();
-    }
-
-    fn move_item(&mut self, from: usize, to: usize) {
-        if from < self.items.len() && to <= self.items.len() {
-            let item = self.items.remove(from);
-            let insert_at = if to > from { to - 1 } else { to };
-            self.items.insert(insert_at.min(self.items.len()), item);
-            self.reorder_items();
-        }
-    }
-
-    fn to_markdown(&self) -> String {
+    fn to_markdown(&self, lang: Language) -> String {
         let mut md = format!("# {}\n\n", self.name);
-        for item in &self.items {
+        for item in self.sorted_indices().into_iter().map(|idx| &self.items[idx]) {
             md.push_str(&format!("## {}\n\n", item.title));
             if !item.comment.is_empty() {
                 md.push_str(&format!("{}\n\n", item.comment));
             }
-            md.push_str(&format!("*Created: {}*\n\n---\n\n", item.created_at));
+            if !item.checklist.is_empty() {
+                for entry in &item.checklist {
+                    md.push_str(&format!("- [{}] {}\n", if entry.done { "x" } else { " " }, entry.text));
+                }
+                md.push('\n');
+            }
+            if !item.tags.is_empty() {
+                let tags: Vec<String> = item.tags.iter().map(|t| format!("#{}", t)).collect();
+                md.push_str(&format!("*{}: {}*  \n", tr(lang, Msg::MarkdownTags), tags.join(" ")));
+            }
+            md.push_str(&fields::to_markdown(&self.fields, &item.fields, lang));
+            md.push_str(&item.links.to_markdown(lang));
+            md.push_str(&attachments::to_markdown(&item.attachments));
+            md.push_str(&releases::to_markdown(&item.releases, lang));
+            if !item.status.is_todo() {
+                md.push_str(&format!("*{} {}*  \n", tr(lang, Msg::StatusLabel), item.status.name(lang)));
+            }
+            if let Some(due) = item.due {
+                md.push_str(&format!("*{} {}*  \n", tr(lang, Msg::DueDateLabel), due.format("%Y-%m-%d")));
+            }
+            if let (Some(start), Some(end)) = (item.start_date, item.end_date) {
+                md.push_str(&format!("*{} {} – {}*  \n", tr(lang, Msg::ScheduleLabel), start.format("%Y-%m-%d"), end.format("%Y-%m-%d")));
+            }
+            if let Some(rule) = item.recurrence {
+                md.push_str(&format!("*{} {}*  \n", tr(lang, Msg::RecurrenceLabel), rule.name(lang)));
+            }
+            let tracked = timetrack::total_seconds(&item.time_entries);
+            if tracked > 0 {
+                md.push_str(&format!("*{}: {}*  \n", tr(lang, Msg::MarkdownTimeSpent), timetrack::format_duration(tracked)));
+            }
+            md.push_str(&format!("*{}: {}*\n\n---\n\n", tr(lang, Msg::MarkdownCreated), item.created_at));
         }
         md
     }
 }
 
-#[derive(Serialize, Deserialize, Clone, Debug)]
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq)]
 struct AppData {
     categories: Vec<Category>,
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    templates: Vec<Template>,
 }
 
 impl Default for AppData {
     fn default() -> Self {
+        Self::with_language(Language::default())
+    }
+}
+
+impl AppData {
+    /// Copy of everything except the categories.
+    fn header(&self) -> Self {
+        let AppData { categories: _, templates } = self;
+        Self {
+            categories: Vec::new(),
+            templates: templates.clone(),
+        }
+    }
+
+    fn with_language(lang: Language) -> Self {
         Self {
-            categories: vec![
-                Category::new("拡張機能"),
-                Category::new("Webアプリ"),
-                Category::new("Windowsアプリ"),
-            ],
+            categories: BuiltinCategory::ALL
+                .iter()
+                .map(|&builtin| Category::new_builtin(builtin, lang))
+                .collect(),
+            templates: Vec::new(),
         }
     }
+
+    /// Marks categories saved before the catalog existed as built-in when
+    /// they still carry a default name.
+    fn detect_builtin_categories(&mut self) {
+        for cat in self.categories.iter_mut().filter(|c| c.builtin.is_none()) {
+            cat.builtin = BuiltinCategory::ALL
+                .iter()
+                .copied()
+                .find(|b| b.is_default_name(&cat.name));
+        }
+    }
+
+    /// Translates the names of built-in categories the user hasn't renamed.
+    /// Returns true if anything changed.
+    fn localize_category_names(&mut self, lang: Language) -> bool {
+        let mut changed = false;
+        for cat in &mut self.categories {
+            if let Some(builtin) = cat.builtin {
+                if builtin.is_default_name(&cat.name) && cat.name != builtin.name(lang) {
+                    cat.name = builtin.name(lang).to_string();
+                    changed = true;
+                }
+            }
+        }
+        changed
+    }
+}
+
+/// What the central panel shows.
+#[derive(Serialize, Deserialize, Clone, Copy, Debug, PartialEq, Eq, Default)]
+enum View {
+    #[default]
+    Board,
+    Calendar,
+    Timeline,
+    Dashboard,
+    Table,
+    Graph,
+}
+
+impl View {
+    const ALL: [View; 6] = [View::Board, View::Table, View::Calendar, View::Timeline, View::Graph, View::Dashboard];
+
+    fn name(self, lang: Language) -> &'static str {
+        let msg = match self {
+            View::Board => Msg::ViewBoard,
+            View::Calendar => Msg::ViewCalendar,
+            View::Timeline => Msg::ViewTimeline,
+            View::Dashboard => Msg::ViewDashboard,
+            View::Table => Msg::ViewTable,
+            View::Graph => Msg::ViewGraph,
+        };
+        tr(lang, msg)
+    }
+}
+
+#[derive(Serialize, Deserialize, Clone, Debug)]
+struct Settings {
+    #[serde(default)]
+    language: Language,
+    #[serde(default)]
+    workspaces: Vec<Workspace>,
+    #[serde(default)]
+    current_workspace: String,
+    #[serde(default)]
+    recent_workspaces: Vec<String>,
+    /// Projects without commits for this many days are marked stale (0 = off).
+    #[serde(default = "default_stale_days")]
+    stale_days: u32,
+    /// Folder last scanned for projects.
+    #[serde(default)]
+    scan_root: String,
+    #[serde(default)]
+    view: View,
+    #[serde(default)]
+    calendar_mode: CalendarMode,
+    #[serde(default)]
+    timeline_zoom: TimelineZoom,
+    /// Columns switched off in the table view.
+    #[serde(default)]
+    hidden_columns: Vec<TableColumn>,
+    /// Day the app was last started, for reminders of what came due since.
+    #[serde(default)]
+    last_opened: Option<chrono::NaiveDate>,
+}
+
+fn default_stale_days() -> u32 {
+    30
+}
+
+impl Default for Settings {
+    fn default() -> Self {
+        Self {
+            language: Language::default(),
+            workspaces: Vec::new(),
+            current_workspace: String::new(),
+            recent_workspaces: Vec::new(),
+            stale_days: default_stale_days(),
+            scan_root: String::new(),
+            view: View::default(),
+            calendar_mode: CalendarMode::default(),
+            timeline_zoom: TimelineZoom::default(),
+            hidden_columns: Vec::new(),
+            last_opened: None,
+        }
+    }
+}
+
+/// Command-line options.
+#[derive(Default)]
+struct CliArgs {
+    workspace: Option<String>,
+    data: Option<PathBuf>,
+}
+
+impl CliArgs {
+    fn parse() -> Self {
+        let mut args = CliArgs::default();
+        let mut iter = std::env::args().skip(1);
+        while let Some(arg) = iter.next() {
+            if let Some(value) = arg.strip_prefix("--workspace=") {
+                args.workspace = Some(value.to_string());
+            } else if arg == "--workspace" {
+                args.workspace = iter.next();
+            } else if let Some(value) = arg.strip_prefix("--data=") {
+                args.data = Some(PathBuf::from(value));
+            } else if arg == "--data" {
+                args.data = iter.next().map(PathBuf::from);
+            }
+        }
+        if args.data.is_none() {
+            args.data = std::env::var_os(DATA_ENV_VAR)
+                .filter(|v| !v.is_empty())
+                .map(PathBuf::from);
+        }
+        args
+    }
+}
+
+/// Read-only state needed to draw cards.
+struct CardContext<'a> {
+    lang: Language,
+    git_statuses: &'a HashMap<String, GitStatus>,
+    stale_days: u32,
+    /// Open blocker titles by item id.
+    blocked: &'a HashMap<String, Vec<String>>,
+    attachments_dir: PathBuf,
+    fields: &'a [FieldDef],
+    read_only: bool,
+}
+
+/// What the board columns show that is costly to work out, kept until the
+/// board, a sort mode or the search changes.
+struct BoardCache {
+    revision: u64,
+    search: String,
+    lang: Language,
+    /// Sort mode and item count of each category.
+    columns: Vec<(SortMode, usize)>,
+    /// Item indices of each category in display order, without the items
+    /// the search filters out.
+    orders: Vec<Vec<usize>>,
+    times: Vec<CategoryTime>,
+    /// Open blocker titles by item id.
+    blocked: HashMap<String, Vec<String>>,
+}
+
+/// Something clicked on a card, applied after the column is drawn.
+enum CardAction {
+    Edit,
+    Delete,
+    ToggleTimer,
 }
 
 struct ProductionManager {
     data: AppData,
     data_path: PathBuf,
+    storage: Storage,
+    data_dir: PathBuf,
+    /// Set when the data file was given with `--data` or the environment
+    /// variable; workspaces are not used then.
+    data_override: bool,
+    window_title: String,
+    /// The board as it was last loaded from or written to disk.
+    base_data: Arc<AppData>,
+    disk_stamp: Option<FileStamp>,
+    last_disk_check: f64,
+    merge: Option<MergeState>,
+    /// Declared before `lock` so pending writes finish before the lock is released.
+    saver: Option<Saver>,
+    save_generation: u64,
+    saved_generation: u64,
+    save_status: SaveStatus,
+    egui_ctx: egui::Context,
+    lock: Option<DataLock>,
+    /// Another window holds the lock; nothing is written to disk.
+    read_only: bool,
+    lock_holder: Option<LockInfo>,
+    show_lock_prompt: bool,
+    lock_prompt_closes_app: bool,
+    settings: Settings,
+    settings_path: PathBuf,
+    show_settings: bool,
+    workspace_dialog: Option<WorkspaceDialog>,
+    scan: Option<ScanState>,
     show_add_popup: bool,
     add_popup_category: usize,
     new_item_title: String,
     new_item_comment: String,
+    new_item_due: String,
+    new_item_template: Option<String>,
     show_edit_popup: bool,
     edit_category: usize,
     edit_item_id: String,
     edit_item_title: String,
+    edit_item_reading: String,
     edit_item_comment: String,
+    edit_item_links: ItemLinks,
+    edit_item_releases: Vec<Release>,
+    edit_item_status: ItemStatus,
+    edit_item_due: String,
+    edit_item_start: String,
+    edit_item_end: String,
+    edit_item_recurrence: Option<Recurrence>,
+    edit_item_tags: String,
+    edit_item_relations: Vec<Relation>,
+    edit_item_attachments: Vec<Attachment>,
+    edit_item_checklist: Vec<ChecklistEntry>,
+    edit_item_fields: FieldValues,
+    checklist_draft: String,
+    time_draft: TimeDraft,
+    time_report: Option<TimeReport>,
+    calendar_anchor: chrono::NaiveDate,
+    timeline_drag: Option<TimelineDrag>,
+    table_sort: Option<(TableColumn, bool)>,
+    title_edit: Option<TitleEdit>,
+    pending_done: Option<PendingDone>,
+    /// Card under the pointer while files are dragged over the window.
+    file_drop_card: Option<String>,
+    /// Column under the pointer while files are dragged over the window.
+    file_drop_column: Option<usize>,
+    template_dialog: Option<TemplateDialog>,
+    field_dialog: Option<FieldDialog>,
+    /// Header search text; narrows the board and the table.
+    search: String,
+    /// Set for the frame in which the search text changed, so the lists
+    /// scroll back to the top.
+    search_changed: bool,
+    search_hits: Option<SearchHits>,
+    board_cache: Option<BoardCache>,
+    /// Bumped whenever the board changes, so caches built from it know when
+    /// to rebuild.
+    data_revision: u64,
+    /// `Settings::last_opened` as it was before this start.
+    previous_open: Option<chrono::NaiveDate>,
+    /// Reminders put away for this session, by item id.
+    dismissed_reminders: HashSet<String>,
     dragging: Option<(usize, usize)>,
     drag_target: Option<(usize, usize)>,
+    /// Measured card heights by item id, with the width they were laid out at.
+    card_heights: HashMap<String, (f32, f32)>,
+    git_watcher: GitWatcher,
+    git_statuses: HashMap<String, GitStatus>,
     status_message: String,
     status_timer: f32,
 }
 
 impl ProductionManager {
-    fn new(cc: &eframe::CreationContext<'_>) -> Self {
+    fn new(cc: &eframe::CreationContext<'_>, args: CliArgs) -> Self {
         Self::setup_fonts(&cc.egui_ctx);
-        
-        let data_path = Self::get_data_path();
-        let data = Self::load_data(&data_path);
-        
+        egui_extras::install_image_loaders(&cc.egui_ctx);
+
+        let mut app = Self::empty(&cc.egui_ctx, Self::get_data_dir());
+        app.data_override = args.data.is_some();
+        app.ensure_workspaces();
+        if let Some(path) = args.data {
+            if let Some(parent) = path.parent().filter(|p| !p.as_os_str().is_empty()) {
+                fs::create_dir_all(parent).ok();
+            }
+            app.set_data_path(path);
+            app.acquire_lock();
+            app.reload_data();
+        } else {
+            let workspace = args.workspace.unwrap_or_else(|| app.settings.current_workspace.clone());
+            app.open_workspace(&workspace);
+        }
+        // A second launch on the same board may simply hand over to the first.
+        app.lock_prompt_closes_app = app.show_lock_prompt;
+        app.note_app_opened();
+        app
+    }
+
+    /// The app with settings from `data_dir` and no board opened yet.
+    fn empty(ctx: &egui::Context, data_dir: PathBuf) -> Self {
+        let settings_path = data_dir.join("settings.json");
+        let settings = Self::load_settings(&settings_path);
+        let previous_open = settings.last_opened;
+
         Self {
-            data,
-            data_path,
+            data: AppData::with_language(settings.language),
+            data_path: PathBuf::new(),
+            storage: Storage::Json(PathBuf::new()),
+            data_dir,
+            data_override: false,
+            window_title: String::new(),
+            base_data: Arc::new(AppData::with_language(settings.language)),
+            disk_stamp: None,
+            last_disk_check: 0.0,
+            merge: None,
+            saver: None,
+            save_generation: 0,
+            saved_generation: 0,
+            save_status: SaveStatus::Idle,
+            egui_ctx: ctx.clone(),
+            lock: None,
+            read_only: false,
+            lock_holder: None,
+            show_lock_prompt: false,
+            lock_prompt_closes_app: false,
+            settings,
+            settings_path,
+            show_settings: false,
+            workspace_dialog: None,
+            scan: None,
             show_add_popup: false,
             add_popup_category: 0,
             new_item_title: String::new(),
             new_item_comment: String::new(),
+            new_item_due: String::new(),
+            new_item_template: None,
             show_edit_popup: false,
             edit_category: 0,
             edit_item_id: String::new(),
             edit_item_title: String::new(),
+            edit_item_reading: String::new(),
             edit_item_comment: String::new(),
+            edit_item_links: ItemLinks::default(),
+            edit_item_releases: Vec::new(),
+            edit_item_status: ItemStatus::default(),
+            edit_item_due: String::new(),
+            edit_item_start: String::new(),
+            edit_item_end: String::new(),
+            edit_item_recurrence: None,
+            edit_item_tags: String::new(),
+            edit_item_relations: Vec::new(),
+            edit_item_attachments: Vec::new(),
+            edit_item_checklist: Vec::new(),
+            edit_item_fields: FieldValues::new(),
+            checklist_draft: String::new(),
+            time_draft: TimeDraft::default(),
+            time_report: None,
+            calendar_anchor: status::today(),
+            timeline_drag: None,
+            table_sort: None,
+            title_edit: None,
+            pending_done: None,
+            file_drop_card: None,
+            file_drop_column: None,
+            template_dialog: None,
+            field_dialog: None,
+            search: String::new(),
+            search_changed: false,
+            search_hits: None,
+            board_cache: None,
+            data_revision: 0,
+            previous_open,
+            dismissed_reminders: HashSet::new(),
             dragging: None,
             drag_target: None,
+            card_heights: HashMap::new(),
+            git_watcher: GitWatcher::new(ctx),
+            git_statuses: HashMap::new(),
             status_message: String::new(),
             status_timer: 0.0,
         }
@@ -157,12 +728,12 @@ impl ProductionManager {
         let mut fonts = FontDefinitions::default();
         
         // Try Noto Sans JP first (bundled), then system fonts
-        let exe_dir = std::env::current_exe().ok().and_then(|p| p.parent().map(|p| p.to_path_buf()));
+        let exe_dir = Self::exe_dir();
         let noto_path = exe_dir.as_ref().map(|d| d.join("fonts/NotoSansJP-Regular.ttf"));
         
         let japanese_loaded = if let Some(ref path) = noto_path {
             if let Ok(font_data) = std::fs::read(path) {
-                fonts.font_data.insert("noto_jp".to_owned(), FontData::from_owned(font_data).into());
+                fonts.font_data.insert("noto_jp".to_owned(), FontData::from_owned(font_data));
                 fonts.families.get_mut(&FontFamily::Proportional).unwrap().insert(0, "noto_jp".to_owned());
                 fonts.families.get_mut(&FontFamily::Monospace).unwrap().insert(0, "noto_jp".to_owned());
                 true
@@ -171,7 +742,7 @@ impl ProductionManager {
         
         if !japanese_loaded {
             if let Ok(font_data) = std::fs::read("C:/Windows/Fonts/YuGothM.ttc") {
-                fonts.font_data.insert("yu_gothic".to_owned(), FontData::from_owned(font_data).into());
+                fonts.font_data.insert("yu_gothic".to_owned(), FontData::from_owned(font_data));
                 fonts.families.get_mut(&FontFamily::Proportional).unwrap().insert(0, "yu_gothic".to_owned());
                 fonts.families.get_mut(&FontFamily::Monospace).unwrap().insert(0, "yu_gothic".to_owned());
             }
@@ -179,7 +750,7 @@ impl ProductionManager {
         
         // Add emoji font at the end as fallback
         if let Ok(font_data) = std::fs::read("C:/Windows/Fonts/seguiemj.ttf") {
-            fonts.font_data.insert("emoji".to_owned(), FontData::from_owned(font_data).into());
+            fonts.font_data.insert("emoji".to_owned(), FontData::from_owned(font_data));
             fonts.families.get_mut(&FontFamily::Proportional).unwrap().push("emoji".to_owned());
             fonts.families.get_mut(&FontFamily::Monospace).unwrap().push("emoji".to_owned());
         }
@@ -198,31 +769,119 @@ impl ProductionManager {
         ctx.set_style(style);
     }
 
-    fn get_data_path() -> PathBuf {
+    fn exe_dir() -> Option<PathBuf> {
+        std::env::current_exe().ok().and_then(|p| p.parent().map(|p| p.to_path_buf()))
+    }
+
+    /// The executable's folder when running in portable mode.
+    fn portable_dir() -> Option<PathBuf> {
+        Self::exe_dir().filter(|dir| dir.join(PORTABLE_MARKER).exists())
+    }
+
+    fn get_data_dir() -> PathBuf {
+        if let Some(dir) = Self::portable_dir() {
+            return dir;
+        }
         let mut path = dirs::data_local_dir().unwrap_or_else(|| PathBuf::from("."));
         path.push("ProductionManager");
         fs::create_dir_all(&path).ok();
-        path.push("data.json");
         path
     }
 
-    fn load_data(path: &PathBuf) -> AppData {
-        if path.exists() {
-            match fs::read_to_string(path) {
-                Ok(content) => serde_json::from_str(&content).unwrap_or_default(),
-                Err(_) => AppData::default(),
-            }
-        } else {
-            AppData::default()
+    fn set_data_path(&mut self, path: PathBuf) {
+        // Dropping the writer flushes what is still pending for the old file.
+        self.saver = None;
+        self.save_status = SaveStatus::Idle;
+        self.storage = Storage::open(&path);
+        self.data_path = path;
+    }
+
+    fn load_data(&self) -> AppData {
+        match self.storage.load() {
+            Ok(Some(data)) => data,
+            _ => AppData::with_language(self.settings.language),
+        }
+    }
+
+    /// Loads `data_path` into `data`, translating untouched default names.
+    fn reload_data(&mut self) {
+        let lang = self.settings.language;
+        self.data = self.load_data();
+        self.data.detect_builtin_categories();
+        self.merge = None;
+        self.mark_synced();
+        if self.data.localize_category_names(lang) {
+            self.save_data();
         }
     }
 
-    fn save_data(&self) {
-        if let Ok(json) = serde_json::to_string_pretty(&self.data) {
-            fs::write(&self.data_path, json).ok();
+    /// Closes popups and drops drag state that refers to the previous board.
+    fn reset_transient_state(&mut self) {
+        self.show_add_popup = false;
+        self.show_edit_popup = false;
+        self.add_popup_category = 0;
+        self.edit_category = 0;
+        self.dragging = None;
+        self.drag_target = None;
+        self.card_heights.clear();
+    }
+
+    /// Hands a snapshot to the background writer, which coalesces rapid
+    /// changes and refuses to overwrite outside edits.
+    fn save_data(&mut self) {
+        self.data_revision += 1;
+        if self.read_only || self.merge.is_some() {
+            return;
+        }
+        if let Some(saver) = &self.saver {
+            self.save_generation += 1;
+            saver.save(self.save_generation, Arc::new(self.data.clone()));
+            self.save_status = SaveStatus::Saving;
         }
     }
 
+    fn load_settings(path: &PathBuf) -> Settings {
+        fs::read_to_string(path)
+            .ok()
+            .and_then(|content| serde_json::from_str(&content).ok())
+            .unwrap_or_default()
+    }
+
+    fn save_settings(&self) {
+        if let Ok(json) = serde_json::to_string_pretty(&self.settings) {
+            fs::write(&self.settings_path, json).ok();
+        }
+    }
+
+    fn title_for(data_path: &std::path::Path) -> String {
+        format!("{} v{} - {}", APP_NAME, APP_VERSION, data_path.display())
+    }
+
+    fn t(&self, msg: Msg) -> &'static str {
+        tr(self.settings.language, msg)
+    }
+
+    fn set_language(&mut self, lang: Language) {
+        self.settings.language = lang;
+        self.save_settings();
+        if self.data.localize_category_names(lang) {
+            self.save_data();
+        }
+    }
+
+    /// Keeps the git watcher in step with the items' local folders.
+    fn watch_git_repos(&mut self) {
+        let paths: BTreeSet<String> = self
+            .data
+            .categories
+            .iter()
+            .flat_map(|c| c.items.iter())
+            .map(|i| i.links.local_path.clone())
+            .filter(|p| !p.is_empty())
+            .collect();
+        self.git_watcher.watch(paths);
+    }
+
     fn show_status(&mut self, message: &str) {
         self.status_message = message.to_string();
         self.status_timer = 3.0;
@@ -230,13 +889,41 @@ impl ProductionManager {
 }
 
 impl ProductionManager {
-    fn render_category(&mut self, ui: &mut egui::Ui, cat_idx: usize, column_height: f32) {
+    /// The board cache, rebuilt if the board, a sort mode or the search
+    /// changed since it was made.
+    fn take_board_cache(&mut self) -> BoardCache {
+        let lang = self.settings.language;
+        let columns: Vec<(SortMode, usize)> = self.data.categories.iter().map(|c| (c.sort, c.items.len())).collect();
+        let current = self.board_cache.take().filter(|cache| {
+            cache.revision == self.data_revision && cache.search == self.search && cache.lang == lang && cache.columns == columns
+        });
+        if let Some(cache) = current {
+            return cache;
+        }
+        BoardCache {
+            revision: self.data_revision,
+            search: self.search.clone(),
+            lang,
+            columns,
+            orders: self
+                .data
+                .categories
+                .iter()
+                .map(|cat| cat.sorted_indices().into_iter().filter(|&idx| self.is_search_hit(&cat.items[idx])).collect())
+                .collect(),
+            times: self.data.categories.iter().map(|cat| CategoryTime::of(&cat.items)).collect(),
+            blocked: self.data.blocked_items(),
+        }
+    }
+
+    fn render_category(&mut self, ui: &mut egui::Ui, cat_idx: usize, column_height: f32, cache: &BoardCache) {
         let cat_name = self.data.categories[cat_idx].name.clone();
         let items_count = self.data.categories[cat_idx].items.len();
+        let tracked = cache.times[cat_idx].seconds();
         let available_width = ui.available_width();
         let scroll_height = (column_height - 180.0).max(100.0);
 
-        egui::Frame::default()
+        let column = egui::Frame::default()
             .fill(egui::Color32::from_rgb(40, 40, 45))
             .rounding(10.0)
             .inner_margin(12.0)
@@ -245,8 +932,20 @@ impl ProductionManager {
                 
                 // Category header (centered)
                 ui.vertical_centered(|ui| {
-                    ui.heading(egui::RichText::new(&cat_name).size(22.0));
-                    ui.label(egui::RichText::new(format!("{} items", items_count)).size(14.0).color(egui::Color32::from_rgb(150, 150, 155)));
+                    ui.horizontal(|ui| {
+                        let [r, g, b, _] = self.data.categories[cat_idx].color(cat_idx).to_srgba_unmultiplied();
+                        let mut color = [r, g, b];
+                        if ui.color_edit_button_srgb(&mut color).on_hover_text(self.t(Msg::CategoryColor)).changed() {
+                            self.data.categories[cat_idx].color = Some(color);
+                            self.save_data();
+                        }
+                        ui.heading(egui::RichText::new(&cat_name).size(22.0));
+                    });
+                    let mut summary = fill(self.t(Msg::ItemsCount), &[&items_count]);
+                    if tracked > 0 {
+                        summary.push_str(&format!("  ⏱ {}", timetrack::format_duration(tracked)));
+                    }
+                    ui.label(egui::RichText::new(summary).size(14.0).color(egui::Color32::from_rgb(150, 150, 155)));
                 });
 
                 ui.add_space(10.0);
@@ -261,15 +960,12 @@ impl ProductionManager {
                             ui.horizontal(|ui| {
                                 ui.label(egui::RichText::new("➕").size(16.0));
                                 ui.add_space(4.0);
-                                ui.label(egui::RichText::new("追加").size(18.0));
+                                ui.label(egui::RichText::new(self.t(Msg::Add)).size(18.0));
                             });
                         }).response.interact(egui::Sense::click());
                     
                     if btn_response.clicked() {
-                        self.show_add_popup = true;
-                        self.add_popup_category = cat_idx;
-                        self.new_item_title.clear();
-                        self.new_item_comment.clear();
+                        self.open_add_popup(cat_idx, None);
                     }
                     if btn_response.hovered() {
                         ui.ctx().set_cursor_icon(egui::CursorIcon::PointingHand);
@@ -281,29 +977,26 @@ impl ProductionManager {
                 // Sort & Export buttons (centered)
                 ui.vertical_centered(|ui| {
                     ui.horizontal(|ui| {
-                        if ui.small_button("A-Z").clicked() {
-                            self.data.categories[cat_idx].sort_by_title();
-                            self.save_data();
-                        }
-                        if ui.small_button("Z-A").clicked() {
-                            self.data.categories[cat_idx].sort_by_title();
-                            self.data.categories[cat_idx].items.reverse();
-                            self.data.categories[cat_idx].reorder_items();
+                        if sorting::render_sort_selector(ui, self.settings.language, cat_idx, &mut self.data.categories[cat_idx].sort) {
                             self.save_data();
                         }
-                        if ui.small_button("📅").clicked() {
-                            self.data.categories[cat_idx].sort_by_date();
-                            self.save_data();
+                        if ui
+                            .add_enabled(!self.read_only, egui::Button::new("🧩").small())
+                            .on_hover_text(fill(self.t(Msg::FieldsTitle), &[&cat_name]))
+                            .clicked()
+                        {
+                            self.open_field_dialog(cat_idx);
                         }
-                        if ui.small_button("📄").clicked() {
+                        if ui.small_button("📄").on_hover_text(self.t(Msg::ExportMarkdownHint)).clicked() {
                             if let Some(path) = rfd::FileDialog::new()
-                                .set_file_name(&format!("{}.md", cat_name))
+                                .set_file_name(format!("{}.md", cat_name))
                                 .add_filter("Markdown", &["md"])
                                 .save_file()
                             {
-                                let md = self.data.categories[cat_idx].to_markdown();
+                                let md = self.data.categories[cat_idx].to_markdown(self.settings.language);
+                                self.export_attachments(self.data.categories[cat_idx].items.iter(), &path);
                                 if fs::write(&path, md).is_ok() {
-                                    self.show_status("Exported!");
+                                    self.show_status(self.t(Msg::Exported));
                                 }
                             }
                         }
@@ -314,31 +1007,93 @@ impl ProductionManager {
                 ui.separator();
                 ui.add_space(8.0);
 
-                // Items scroll area with unique ID
+                // Items scroll area with unique ID. Only the cards inside the
+                // viewport are laid out; the rest is covered by cached heights.
                 let scroll_id = egui::Id::new(format!("cat_scroll_{}", cat_idx));
-                egui::ScrollArea::vertical()
+                let card_ctx = CardContext {
+                    lang: self.settings.language,
+                    git_statuses: &self.git_statuses,
+                    stale_days: self.settings.stale_days,
+                    blocked: &cache.blocked,
+                    attachments_dir: self.attachments_dir(),
+                    fields: &self.data.categories[cat_idx].fields,
+                    read_only: self.read_only,
+                };
+                let items = &self.data.categories[cat_idx].items;
+                let display_order = &cache.orders[cat_idx];
+                let card_heights = &mut self.card_heights;
+                let dragging = self.dragging;
+                let drag_target = &mut self.drag_target;
+                let file_drop_card = &mut self.file_drop_card;
+                let files_over = ui.ctx().input(|i| !i.raw.hovered_files.is_empty() || !i.raw.dropped_files.is_empty());
+                let pointer = ui.ctx().pointer_latest_pos();
+                let mut action = None;
+
+                let mut scroll = egui::ScrollArea::vertical();
+                if self.search_changed {
+                    scroll = scroll.vertical_scroll_offset(0.0);
+                }
+                scroll
                     .id_salt(scroll_id)
                     .max_height(scroll_height)
                     .auto_shrink([false, false])
-                    .show(ui, |ui| {
-                        let item_width = ui.available_width();
-                        
-                        let items: Vec<_> = self.data.categories[cat_idx]
-                            .items.iter().enumerate()
-                            .map(|(i, item)| (i, item.id.clone(), item.title.clone(), item.comment.clone()))
-                            .collect();
-
-                        for (item_idx, item_id, title, comment) in items {
-                            self.render_item(ui, cat_idx, item_idx, &item_id, &title, &comment);
-                            ui.add_space(6.0);
+                    .show_viewport(ui, |ui, viewport| {
+                        let width = ui.available_width();
+                        let height_of = |card_heights: &HashMap<String, (f32, f32)>, item: &Item| {
+                            card_heights
+                                .get(&item.id)
+                                .filter(|(w, _)| (w - width).abs() < 0.5)
+                                .map_or(ESTIMATED_CARD_HEIGHT, |&(_, h)| h)
+                        };
+
+                        let mut top = 0.0;
+                        let mut first = display_order.len();
+                        for (pos, &idx) in display_order.iter().enumerate() {
+                            let h = height_of(card_heights, &items[idx]);
+                            if top + h >= viewport.min.y {
+                                first = pos;
+                                break;
+                            }
+                            top += h + CARD_SPACING;
                         }
+                        ui.add_space(top);
+
+                        let mut y = top;
+                        let mut last = first;
+                        for (pos, &idx) in display_order.iter().enumerate().skip(first) {
+                            let item = &items[idx];
+                            if y > viewport.max.y {
+                                break;
+                            }
+                            let is_dragging = dragging == Some((cat_idx, idx));
+                            let is_target = *drag_target == Some((cat_idx, idx))
+                                || (files_over && file_drop_card.as_deref() == Some(item.id.as_str()));
+                            let (rect, card_action) = Self::render_item(ui, &card_ctx, item, is_dragging, is_target);
+                            if files_over && pointer.is_some_and(|p| rect.contains(p)) {
+                                *file_drop_card = Some(item.id.clone());
+                            }
+                            if let Some(card_action) = card_action {
+                                action = Some((idx, card_action));
+                            }
+                            let measured = rect.height();
+                            if card_heights.get(&item.id) != Some(&(width, measured)) {
+                                card_heights.insert(item.id.clone(), (width, measured));
+                                ui.ctx().request_repaint();
+                            }
+                            ui.add_space(CARD_SPACING);
+                            y += measured + CARD_SPACING;
+                            last = pos + 1;
+                        }
+
+                        let below: f32 = display_order[last..].iter().map(|&idx| height_of(card_heights, &items[idx]) + CARD_SPACING).sum();
+                        ui.add_space(below);
 
                         // Drop zone at bottom
-                        if self.dragging.is_some() {
-                            let is_target = self.drag_target == Some((cat_idx, self.data.categories[cat_idx].items.len()));
+                        if dragging.is_some() {
+                            let is_target = *drag_target == Some((cat_idx, items.len()));
                             let response = ui.allocate_response(egui::vec2(200.0, 24.0), egui::Sense::hover());
                             if response.hovered() {
-                                self.drag_target = Some((cat_idx, self.data.categories[cat_idx].items.len()));
+                                *drag_target = Some((cat_idx, items.len()));
                             }
                             let color = if is_target {
                                 egui::Color32::from_rgb(60, 120, 60)
@@ -348,14 +1103,26 @@ impl ProductionManager {
                             ui.painter().rect_filled(response.rect, 4.0, color);
                         }
                     });
-            });
-    }
 
+                if let Some((item_idx, action)) = action {
+                    self.apply_card_action(cat_idx, item_idx, action);
+                }
+            });
 
-    fn render_item(&mut self, ui: &mut egui::Ui, cat_idx: usize, item_idx: usize, item_id: &str, title: &str, comment: &str) {
-        let is_dragging = self.dragging == Some((cat_idx, item_idx));
-        let is_target = self.drag_target == Some((cat_idx, item_idx));
+        let files_over = ui.ctx().input(|i| !i.raw.hovered_files.is_empty() || !i.raw.dropped_files.is_empty());
+        if files_over && !self.read_only {
+            if ui.ctx().pointer_latest_pos().is_some_and(|p| column.response.rect.contains(p)) {
+                self.file_drop_column = Some(cat_idx);
+            }
+            if self.file_drop_column == Some(cat_idx) {
+                import::highlight_drop_column(ui, column.response.rect);
+            }
+        }
+    }
 
+    /// Draws one card. Returns its rect and what was clicked, if anything.
+    fn render_item(ui: &mut egui::Ui, card_ctx: &CardContext, item: &Item, is_dragging: bool, is_target: bool) -> (egui::Rect, Option<CardAction>) {
+        let lang = card_ctx.lang;
         let frame_color = if is_dragging {
             egui::Color32::from_rgb(70, 70, 90)
         } else if is_target {
@@ -363,127 +1130,404 @@ impl ProductionManager {
         } else {
             egui::Color32::from_rgb(55, 55, 60)
         };
+        let timing = item.time_entries.iter().any(TimeEntry::is_running);
+        let stroke = if timing {
+            egui::Stroke::new(1.5, egui::Color32::from_rgb(230, 160, 90))
+        } else {
+            egui::Stroke::NONE
+        };
+        let mut action = None;
 
-        let item_id_owned = item_id.to_string();
-        let title_owned = title.to_string();
-        let comment_owned = comment.to_string();
-
-        egui::Frame::default()
+        let response = egui::Frame::default()
             .fill(frame_color)
+            .stroke(stroke)
             .rounding(6.0)
             .inner_margin(10.0)
             .show(ui, |ui| {
                 ui.set_width(ui.available_width());
                 
                 ui.vertical(|ui| {
-                    ui.strong(&title_owned);
-                    if !comment_owned.is_empty() {
+                    ui.horizontal_wrapped(|ui| {
+                        ui.strong(&item.title);
+                        releases::render_version_badge(ui, lang, &item.releases);
+                    });
+                    if !item.tags.is_empty() {
+                        ui.horizontal_wrapped(|ui| tags::render_tags(ui, &item.tags));
+                    }
+                    if !item.comment.is_empty() {
                         ui.add_space(4.0);
-                        ui.label(egui::RichText::new(&comment_owned).size(14.0).color(egui::Color32::from_rgb(180, 180, 185)));
+                        ui.label(egui::RichText::new(&item.comment).size(14.0).color(egui::Color32::from_rgb(180, 180, 185)));
                     }
+                    fields::render_field_values(ui, lang, card_ctx.fields, &item.fields);
+                    status::render_status_line(ui, lang, item);
+                    recurrence::render_badge(ui, lang, item);
+                    if let Some(blockers) = card_ctx.blocked.get(&item.id) {
+                        relations::render_blocked_badge(ui, lang, blockers);
+                    }
+                    templates::render_checklist_progress(ui, lang, &item.checklist);
+                    if let Some(status) = card_ctx.git_statuses.get(&item.links.local_path) {
+                        ui.add_space(4.0);
+                        gitstatus::render_git_status(ui, lang, status, card_ctx.stale_days);
+                    }
+                    attachments::render_thumbnail(ui, &card_ctx.attachments_dir, item);
                 });
 
                 ui.add_space(8.0);
 
                 ui.horizontal(|ui| {
-                    if ui.button("編集").clicked() {
-                        self.show_edit_popup = true;
-                        self.edit_category = cat_idx;
-                        self.edit_item_id = item_id_owned.clone();
-                        self.edit_item_title = title_owned.clone();
-                        self.edit_item_comment = comment_owned.clone();
+                    links::render_link_icons(ui, lang, &item.links);
+                    if !item.links.is_empty() {
+                        ui.add_space(8.0);
+                    }
+                    if ui.button(tr(lang, Msg::Edit)).clicked() {
+                        action = Some(CardAction::Edit);
                     }
                     ui.add_space(8.0);
-                    if ui.button("削除").clicked() {
-                        self.data.categories[cat_idx].remove_item(&item_id_owned);
-                        self.save_data();
-                        self.show_status("削除しました");
+                    if ui.add_enabled(!card_ctx.read_only, egui::Button::new(tr(lang, Msg::Delete))).clicked() {
+                        action = Some(CardAction::Delete);
+                    }
+                    ui.add_space(8.0);
+                    let (icon, hint) = if timing { ("⏹", Msg::StopTimer) } else { ("▶", Msg::StartTimer) };
+                    if ui.button(icon).on_hover_text(tr(lang, hint)).clicked() {
+                        action = Some(CardAction::ToggleTimer);
+                    }
+                    let tracked = timetrack::total_seconds(&item.time_entries);
+                    if tracked > 0 {
+                        ui.label(egui::RichText::new(timetrack::format_duration(tracked)).size(13.0).color(egui::Color32::from_rgb(150, 150, 155)));
                     }
                 });
-            });
+            })
+            .response;
+        (response.rect, action)
+    }
+
+    fn apply_card_action(&mut self, cat_idx: usize, item_idx: usize, action: CardAction) {
+        let Some(item) = self.data.categories[cat_idx].items.get(item_idx) else {
+            return;
+        };
+        match action {
+            CardAction::Edit => {
+                self.show_edit_popup = true;
+                self.edit_category = cat_idx;
+                self.edit_item_id = item.id.clone();
+                self.edit_item_title = item.title.clone();
+                self.edit_item_reading = item.reading.clone();
+                self.edit_item_comment = item.comment.clone();
+                self.edit_item_links = item.links.clone();
+                self.edit_item_releases = item.releases.clone();
+                self.edit_item_status = item.status;
+                self.edit_item_due = item.due.map(|d| d.format("%Y-%m-%d").to_string()).unwrap_or_default();
+                self.edit_item_start = item.start_date.map(|d| d.format("%Y-%m-%d").to_string()).unwrap_or_default();
+                self.edit_item_recurrence = item.recurrence;
+                self.edit_item_end = item.end_date.map(|d| d.format("%Y-%m-%d").to_string()).unwrap_or_default();
+                self.edit_item_tags = tags::join_tags(&item.tags);
+                self.edit_item_relations = item.relations.clone();
+                self.edit_item_attachments = item.attachments.clone();
+                self.edit_item_checklist = item.checklist.clone();
+                self.edit_item_fields = item.fields.clone();
+                self.checklist_draft.clear();
+                self.time_draft = TimeDraft::default();
+            }
+            CardAction::Delete if self.read_only => {}
+            CardAction::Delete => {
+                let id = item.id.clone();
+                attachments::remove_files(&self.attachments_dir(), &item.attachments);
+                self.data.categories[cat_idx].remove_item(&id);
+                self.data.forget_item(&id);
+                self.card_heights.remove(&id);
+                self.save_data();
+                self.show_status(self.t(Msg::Deleted));
+            }
+            CardAction::ToggleTimer => self.toggle_timer(cat_idx, item_idx),
+        }
+    }
+
+    /// Opens the add popup for a column, optionally with a due date filled in.
+    fn open_add_popup(&mut self, cat_idx: usize, due: Option<chrono::NaiveDate>) {
+        self.show_add_popup = true;
+        self.add_popup_category = cat_idx;
+        self.new_item_due = due.map(|d| d.format("%Y-%m-%d").to_string()).unwrap_or_default();
+        self.apply_template(self.data.categories[cat_idx].default_template.clone());
     }
 
     fn render_add_popup(&mut self, ctx: &egui::Context) {
-        egui::Window::new("新規追加")
+        egui::Window::new(self.t(Msg::NewItem))
+            .id(egui::Id::new("add_popup"))
             .collapsible(false)
             .resizable(false)
             .anchor(egui::Align2::CENTER_CENTER, [0.0, 0.0])
             .show(ctx, |ui| {
-                let cat_name = self.data.categories[self.add_popup_category].name.clone();
-                ui.label(format!("カテゴリ: {}", cat_name));
+                ui.horizontal(|ui| {
+                    ui.label(self.t(Msg::CategoryLabel));
+                    let cat_name = self.data.categories[self.add_popup_category].name.clone();
+                    let previous = self.add_popup_category;
+                    egui::ComboBox::from_id_salt("add_popup_category")
+                        .selected_text(cat_name)
+                        .show_ui(ui, |ui| {
+                            for (cat_idx, cat) in self.data.categories.iter().enumerate() {
+                                ui.selectable_value(&mut self.add_popup_category, cat_idx, &cat.name);
+                            }
+                        });
+                    let default = self.data.categories[self.add_popup_category].default_template.clone();
+                    if self.add_popup_category != previous && default.is_some() {
+                        self.apply_template(default);
+                    }
+                });
+                self.render_template_picker(ui);
 
                 ui.add_space(12.0);
 
                 ui.horizontal(|ui| {
-                    ui.label("タイトル:");
+                    ui.label(self.t(Msg::TitleLabel));
                     ui.add_sized([250.0, 20.0], egui::TextEdit::singleline(&mut self.new_item_title));
                 });
 
                 ui.add_space(8.0);
 
-                ui.label("コメント:");
+                ui.label(self.t(Msg::CommentLabel));
                 ui.add(egui::TextEdit::multiline(&mut self.new_item_comment)
                     .desired_width(320.0)
                     .desired_rows(4));
 
+                ui.add_space(8.0);
+
+                ui.horizontal(|ui| {
+                    ui.label(self.t(Msg::DueDateLabel));
+                    ui.add(egui::TextEdit::singleline(&mut self.new_item_due).desired_width(90.0).hint_text("YYYY-MM-DD"));
+                });
+
                 ui.add_space(12.0);
 
                 ui.horizontal(|ui| {
-                    if ui.button("キャンセル").clicked() {
+                    if ui.button(self.t(Msg::Cancel)).clicked() {
                         self.show_add_popup = false;
                     }
                     ui.add_space(20.0);
-                    let can_add = !self.new_item_title.trim().is_empty();
-                    if ui.add_enabled(can_add, egui::Button::new("追加")).clicked() {
-                        self.data.categories[self.add_popup_category].add_item(
+                    let due = status::parse_date(&self.new_item_due);
+                    let can_add = !self.new_item_title.trim().is_empty()
+                        && (due.is_some() || self.new_item_due.trim().is_empty());
+                    if ui.add_enabled(can_add, egui::Button::new(self.t(Msg::Add))).clicked() {
+                        let item = self.data.categories[self.add_popup_category].add_item(
                             self.new_item_title.trim().to_string(),
                             self.new_item_comment.trim().to_string(),
                         );
+                        item.due = due;
+                        let mut status = item.status;
+                        if let Some(template) = self.new_item_template.as_deref().and_then(|id| self.data.templates.iter().find(|t| t.id == id)) {
+                            template.apply_extras(item);
+                            status = template.status;
+                        }
                         self.save_data();
+                        // Like any other status change, so blockers and repeats apply.
+                        let item_idx = self.data.categories[self.add_popup_category].items.len() - 1;
+                        self.request_status(self.add_popup_category, item_idx, status);
                         self.show_add_popup = false;
-                        self.show_status("追加しました");
+                        self.show_status(self.t(Msg::Added));
                     }
                 });
             });
     }
 
-    fn render_edit_popup(&mut self, ctx: &egui::Context) {
-        egui::Window::new("編集")
+    fn render_settings_popup(&mut self, ctx: &egui::Context) {
+        egui::Window::new(self.t(Msg::Settings))
+            .id(egui::Id::new("settings_popup"))
             .collapsible(false)
             .resizable(false)
             .anchor(egui::Align2::CENTER_CENTER, [0.0, 0.0])
             .show(ctx, |ui| {
                 ui.horizontal(|ui| {
-                    ui.label("タイトル:");
-                    ui.add_sized([250.0, 20.0], egui::TextEdit::singleline(&mut self.edit_item_title));
+                    ui.label(self.t(Msg::LanguageLabel));
+                    let current = self.settings.language;
+                    let mut selected = current;
+                    egui::ComboBox::from_id_salt("language_select")
+                        .selected_text(current.native_name())
+                        .show_ui(ui, |ui| {
+                            for lang in Language::ALL {
+                                ui.selectable_value(&mut selected, lang, lang.native_name());
+                            }
+                        });
+                    if selected != current {
+                        self.set_language(selected);
+                    }
                 });
 
                 ui.add_space(8.0);
+                ui.horizontal(|ui| {
+                    ui.label(self.t(Msg::StaleDaysLabel));
+                    let response = ui.add(egui::DragValue::new(&mut self.settings.stale_days).range(0..=3650));
+                    ui.label(self.t(Msg::StaleDaysUnit));
+                    if response.changed() {
+                        self.save_settings();
+                    }
+                });
 
-                ui.label("コメント:");
-                ui.add(egui::TextEdit::multiline(&mut self.edit_item_comment)
-                    .desired_width(320.0)
-                    .desired_rows(4));
+                ui.add_space(8.0);
+                self.render_storage_settings(ui);
+
+                ui.add_space(12.0);
+
+                if ui.button(self.t(Msg::Close)).clicked() {
+                    self.show_settings = false;
+                }
+            });
+    }
+
+    /// Writes the releases being edited as a CHANGELOG.md, next to the
+    /// project when it has a local folder.
+    fn export_changelog(&mut self) {
+        let mut dialog = rfd::FileDialog::new()
+            .set_file_name("CHANGELOG.md")
+            .add_filter("Markdown", &["md"]);
+        let local_path = self.edit_item_links.local_path.trim();
+        if !local_path.is_empty() {
+            dialog = dialog.set_directory(local_path);
+        }
+        if let Some(path) = dialog.save_file() {
+            let md = releases::to_changelog(self.edit_item_title.trim(), &releases::normalized(&self.edit_item_releases));
+            if fs::write(&path, md).is_ok() {
+                self.show_status(self.t(Msg::Exported));
+            }
+        }
+    }
+
+    fn render_edit_popup(&mut self, ctx: &egui::Context) {
+        egui::Window::new(self.t(Msg::EditItem))
+            .id(egui::Id::new("edit_popup"))
+            .collapsible(false)
+            .resizable(false)
+            .anchor(egui::Align2::CENTER_CENTER, [0.0, 0.0])
+            .show(ctx, |ui| {
+                // Long release and time lists scroll; the buttons stay put.
+                egui::ScrollArea::vertical()
+                    .id_salt("edit_popup_body")
+                    .max_height((ctx.screen_rect().height() - 160.0).max(200.0))
+                    .show(ui, |ui| {
+                        ui.horizontal(|ui| {
+                            ui.label(self.t(Msg::TitleLabel));
+                            ui.add_sized([250.0, 20.0], egui::TextEdit::singleline(&mut self.edit_item_title));
+                        });
+                        ui.horizontal(|ui| {
+                            ui.label(self.t(Msg::ReadingLabel));
+                            let hint = self.t(Msg::ReadingHint);
+                            ui.add_sized([250.0, 20.0], egui::TextEdit::singleline(&mut self.edit_item_reading).hint_text(hint));
+                        });
+
+                        ui.add_space(8.0);
+
+                        ui.label(self.t(Msg::CommentLabel));
+                        ui.add(egui::TextEdit::multiline(&mut self.edit_item_comment)
+                            .desired_width(320.0)
+                            .desired_rows(4));
+
+                        ui.add_space(8.0);
+
+                        ui.horizontal(|ui| {
+                            ui.label(self.t(Msg::TagsLabel));
+                            let hint = self.t(Msg::TagsHint);
+                            ui.add(egui::TextEdit::singleline(&mut self.edit_item_tags)
+                                .desired_width(260.0)
+                                .hint_text(hint));
+                        });
+
+                        ui.add_space(8.0);
+
+                        let field_defs = &self.data.categories[self.edit_category].fields;
+                        if !field_defs.is_empty() {
+                            ui.label(self.t(Msg::FieldsLabel));
+                            fields::render_field_editor(ui, self.settings.language, field_defs, &mut self.edit_item_fields);
+                            ui.add_space(8.0);
+                        }
+
+                        ui.label(self.t(Msg::ChecklistLabel));
+                        templates::render_checklist_editor(ui, self.settings.language, &mut self.edit_item_checklist, &mut self.checklist_draft);
+
+                        ui.add_space(8.0);
+
+                        status::render_status_editor(ui, self.settings.language, &mut self.edit_item_status, &mut self.edit_item_due);
+                        status::render_schedule_editor(ui, self.settings.language, &mut self.edit_item_start, &mut self.edit_item_end);
+                        recurrence::render_recurrence_editor(ui, self.settings.language, &mut self.edit_item_recurrence);
+
+                        ui.add_space(8.0);
+
+                        ui.label(self.t(Msg::LinksLabel));
+                        links::render_link_editor(ui, self.settings.language, &mut self.edit_item_links);
+
+                        ui.add_space(8.0);
+
+                        ui.horizontal(|ui| {
+                            ui.label(self.t(Msg::ReleasesLabel));
+                            let has_releases = self.edit_item_releases.iter().any(|r| !r.version.trim().is_empty());
+                            if ui
+                                .add_enabled(has_releases, egui::Button::new(self.t(Msg::ExportChangelog)).small())
+                                .on_hover_text(self.t(Msg::ExportChangelogHint))
+                                .clicked()
+                            {
+                                self.export_changelog();
+                            }
+                        });
+                        releases::render_release_editor(ui, self.settings.language, &mut self.edit_item_releases);
+
+                        ui.add_space(8.0);
+
+                        ui.label(self.t(Msg::RelationsLabel));
+                        relations::render_relation_editor(ui, self.settings.language, &self.data, &self.edit_item_id, &mut self.edit_item_relations);
+
+                        ui.add_space(8.0);
+
+                        self.render_attachment_gallery(ui);
+
+                        ui.add_space(8.0);
+
+                        self.render_time_entries(ui);
+                    });
 
                 ui.add_space(12.0);
 
                 ui.horizontal(|ui| {
-                    if ui.button("キャンセル").clicked() {
+                    if ui.button(self.t(Msg::Cancel)).clicked() {
+                        if let Some(item) = self.data.categories[self.edit_category].items.iter().find(|i| i.id == self.edit_item_id) {
+                            self.discard_attachments(&item.attachments, &self.edit_item_attachments);
+                        }
                         self.show_edit_popup = false;
                     }
                     ui.add_space(20.0);
-                    let can_save = !self.edit_item_title.trim().is_empty();
-                    if ui.add_enabled(can_save, egui::Button::new("保存")).clicked() {
+                    let due = status::parse_date(&self.edit_item_due);
+                    let start = status::parse_date(&self.edit_item_start);
+                    let end = status::parse_date(&self.edit_item_end);
+                    let can_save = !self.edit_item_title.trim().is_empty()
+                        && (due.is_some() || self.edit_item_due.trim().is_empty())
+                        && (start.is_some() || self.edit_item_start.trim().is_empty())
+                        && (end.is_some() || self.edit_item_end.trim().is_empty())
+                        && fields::all_valid(&self.data.categories[self.edit_category].fields, &self.edit_item_fields);
+                    if ui.add_enabled(can_save, egui::Button::new(self.t(Msg::Save))).clicked() {
                         if let Some(item) = self.data.categories[self.edit_category]
                             .items.iter_mut()
                             .find(|i| i.id == self.edit_item_id)
                         {
                             item.title = self.edit_item_title.trim().to_string();
+                            item.reading = self.edit_item_reading.trim().to_string();
                             item.comment = self.edit_item_comment.trim().to_string();
+                            item.links = self.edit_item_links.trimmed();
+                            item.releases = releases::normalized(&self.edit_item_releases);
+                            item.due = due;
+                            item.start_date = start;
+                            item.end_date = end;
+                            item.recurrence = self.edit_item_recurrence;
+                            item.tags = tags::parse_tags(&self.edit_item_tags);
+                            item.relations = self.edit_item_relations.clone();
+                            item.fields = fields::normalized(&self.edit_item_fields);
+                            item.checklist = self.edit_item_checklist.iter().filter(|e| !e.text.trim().is_empty()).cloned().collect();
+                            let previous = std::mem::replace(&mut item.attachments, self.edit_item_attachments.clone());
+                            item.touch();
+                            self.discard_attachments(&self.edit_item_attachments, &previous);
                         }
                         self.save_data();
+                        // Last, so the blocker check sees the saved relations.
+                        if let Some(item_idx) = self.data.categories[self.edit_category].items.iter().position(|i| i.id == self.edit_item_id) {
+                            self.request_status(self.edit_category, item_idx, self.edit_item_status);
+                        }
                         self.show_edit_popup = false;
-                        self.show_status("保存しました");
+                        self.show_status(self.t(Msg::Saved));
                     }
                 });
             });
@@ -492,6 +1536,24 @@ impl ProductionManager {
 
 impl eframe::App for ProductionManager {
     fn update(&mut self, ctx: &egui::Context, _frame: &mut eframe::Frame) {
+        self.poll_saver();
+
+        let title = Self::title_for(&self.data_path);
+        if title != self.window_title {
+            ctx.send_viewport_cmd(egui::ViewportCommand::Title(title.clone()));
+            self.window_title = title;
+        }
+
+        let now = ctx.input(|i| i.time);
+        if now - self.last_disk_check >= merge::CHECK_INTERVAL_SECS {
+            self.last_disk_check = now;
+            self.check_external_changes();
+            self.poll_lock(ctx);
+            self.watch_git_repos();
+        }
+        self.git_watcher.poll(&mut self.git_statuses);
+        ctx.request_repaint_after(std::time::Duration::from_secs_f64(merge::CHECK_INTERVAL_SECS));
+
         if self.status_timer > 0.0 {
             self.status_timer -= ctx.input(|i| i.unstable_dt);
             ctx.request_repaint();
@@ -501,7 +1563,49 @@ impl eframe::App for ProductionManager {
             ui.add_space(4.0);
             ui.horizontal(|ui| {
                 ui.heading(format!("🎨 {} v{}", APP_NAME, APP_VERSION));
+                ui.add_space(12.0);
+                if self.data_override {
+                    ui.label(format!("📄 {}", self.data_path.display()))
+                        .on_hover_text(self.t(Msg::DataOverrideHint));
+                } else {
+                    self.render_workspace_menu(ui);
+                }
+                ui.add_space(12.0);
+                let mut view = self.settings.view;
+                for option in View::ALL {
+                    ui.selectable_value(&mut view, option, option.name(self.settings.language));
+                }
+                if view != self.settings.view {
+                    self.settings.view = view;
+                    self.save_settings();
+                }
+                ui.add_space(12.0);
+                self.search_changed = search::render_search_box(ui, self.settings.language, &mut self.search);
                 ui.with_layout(egui::Layout::right_to_left(egui::Align::Center), |ui| {
+                    if ui.button("⚙").on_hover_text(self.t(Msg::Settings)).clicked() {
+                        self.show_settings = !self.show_settings;
+                    }
+                    if ui.button("⏱").on_hover_text(self.t(Msg::TimeReportTitle)).clicked() {
+                        self.open_time_report();
+                    }
+                    if ui
+                        .add_enabled(!self.read_only, egui::Button::new("📝"))
+                        .on_hover_text(self.t(Msg::TemplatesTitle))
+                        .clicked()
+                    {
+                        self.open_template_dialog();
+                    }
+                    if ui
+                        .add_enabled(!self.read_only, egui::Button::new("📂"))
+                        .on_hover_text(self.t(Msg::ScanFolder))
+                        .clicked()
+                    {
+                        self.choose_scan_folder();
+                    }
+                    self.render_reminders(ui);
+                    self.render_save_indicator(ui);
+                    self.render_timer_indicator(ui);
+                    self.render_read_only_banner(ui);
                     if !self.status_message.is_empty() && self.status_timer > 0.0 {
                         ui.label(egui::RichText::new(&self.status_message).color(egui::Color32::from_rgb(100, 200, 100)));
                     }
@@ -510,17 +1614,28 @@ impl eframe::App for ProductionManager {
             ui.add_space(4.0);
         });
 
-        egui::CentralPanel::default().show(ctx, |ui| {
-            let panel_height = ui.available_height();
-            let num_cats = self.data.categories.len();
-            
-            ui.columns(num_cats, |columns| {
-                for (cat_idx, col) in columns.iter_mut().enumerate() {
-                    col.vertical(|ui| {
-                        self.render_category(ui, cat_idx, panel_height - 16.0);
-                    });
-                }
-            });
+        self.refresh_search();
+
+        egui::CentralPanel::default().show(ctx, |ui| match self.settings.view {
+            View::Board => {
+                let panel_height = ui.available_height();
+                let num_cats = self.data.categories.len();
+                let cache = self.take_board_cache();
+
+                ui.columns(num_cats, |columns| {
+                    for (cat_idx, col) in columns.iter_mut().enumerate() {
+                        col.vertical(|ui| {
+                            self.render_category(ui, cat_idx, panel_height - 16.0, &cache);
+                        });
+                    }
+                });
+                self.board_cache = Some(cache);
+            }
+            View::Calendar => self.render_calendar(ui),
+            View::Timeline => self.render_timeline(ui),
+            View::Dashboard => self.render_dashboard(ui),
+            View::Table => self.render_table(ui),
+            View::Graph => self.render_graph(ui),
         });
 
         if self.show_add_popup {
@@ -530,10 +1645,33 @@ impl eframe::App for ProductionManager {
         if self.show_edit_popup {
             self.render_edit_popup(ctx);
         }
+
+        if self.show_settings {
+            self.render_settings_popup(ctx);
+        }
+
+        self.render_workspace_dialog(ctx);
+        self.render_scan_dialog(ctx);
+        self.render_time_report(ctx);
+        self.render_merge_dialog(ctx);
+        self.render_lock_prompt(ctx);
+        self.render_blocked_warning(ctx);
+        self.render_template_dialog(ctx);
+        self.render_field_dialog(ctx);
+        self.handle_paste_shortcut(ctx);
+        self.handle_dropped_files(ctx);
+    }
+
+    fn on_exit(&mut self, _gl: Option<&eframe::glow::Context>) {
+        if let Some(saver) = self.saver.take() {
+            saver.flush();
+        }
+        self.lock = None;
     }
 }
 
 fn main() -> eframe::Result<()> {
+    let args = CliArgs::parse();
     let options = eframe::NativeOptions {
         viewport: egui::ViewportBuilder::default()
             .with_inner_size([850.0, 600.0])
@@ -545,6 +1683,6 @@ fn main() -> eframe::Result<()> {
     eframe::run_native(
         APP_NAME,
         options,
-        Box::new(|cc| Ok(Box::new(ProductionManager::new(cc)))),
+        Box::new(|cc| Ok(Box::new(ProductionManager::new(cc, args)))),
     )
 }
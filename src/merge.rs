@@ -0,0 +1,542 @@
+//! Detects edits made to the data file outside the app and reconciles them
+//! with local changes, item by item.
+
+use crate::i18n::{fill, tr, Language, Msg};
+use crate::saver::Saver;
+use crate::{AppData, Category, Item, ProductionManager};
+use eframe::egui;
+use std::collections::BTreeSet;
+use std::fs;
+use std::path::Path;
+use std::sync::Arc;
+use std::time::SystemTime;
+
+/// How often the data file is checked for outside modifications.
+pub const CHECK_INTERVAL_SECS: f64 = 1.0;
+
+/// Identifies one version of a file on disk.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub struct FileStamp {
+    modified: SystemTime,
+    len: u64,
+}
+
+impl FileStamp {
+    pub fn of(path: &Path) -> Option<Self> {
+        let meta = fs::metadata(path).ok()?;
+        Some(Self {
+            modified: meta.modified().ok()?,
+            len: meta.len(),
+        })
+    }
+}
+
+/// What a conflict is about.
+#[derive(Clone, Debug, PartialEq)]
+enum ConflictKey {
+    Item(String),
+    /// Templates.
+    Board,
+    /// Name, color, sorting, default template and fields of the category at
+    /// this position.
+    Category(usize),
+    /// The list of categories, when one side added or removed some and the
+    /// other changed any of them.
+    Categories,
+}
+
+/// Something that was changed differently on both sides.
+pub struct MergeConflict {
+    key: ConflictKey,
+    use_remote: bool,
+}
+
+/// An open merge between the in-memory board and the file on disk.
+pub struct MergeState {
+    base: AppData,
+    local: AppData,
+    remote: AppData,
+    remote_stamp: Option<FileStamp>,
+    conflicts: Vec<MergeConflict>,
+    auto_merged: usize,
+}
+
+/// Item `id` together with the index of the category holding it.
+fn find_item<'a>(data: &'a AppData, id: &str) -> Option<(usize, &'a Item)> {
+    data.categories.iter().enumerate().find_map(|(cat_idx, cat)| {
+        cat.items.iter().find(|item| item.id == id).map(|item| (cat_idx, item))
+    })
+}
+
+fn item_ids(data: &AppData) -> impl Iterator<Item = &str> {
+    data.categories.iter().flat_map(|c| c.items.iter().map(|i| i.id.as_str()))
+}
+
+/// The categories of `data` without their items.
+fn category_headers(data: &AppData) -> Vec<Category> {
+    data.categories.iter().map(Category::header).collect()
+}
+
+/// Three boards whose categories can be matched up by position.
+fn aligned(base: &AppData, local: &AppData, remote: &AppData) -> bool {
+    base.categories.len() == local.categories.len() && base.categories.len() == remote.categories.len()
+}
+
+/// How a part of the board changed since the common base.
+#[derive(PartialEq)]
+enum Change {
+    None,
+    OneSided,
+    Both,
+}
+
+fn change<T: PartialEq>(base: T, local: T, remote: T) -> Change {
+    if local == remote {
+        Change::None
+    } else if local == base || remote == base {
+        Change::OneSided
+    } else {
+        Change::Both
+    }
+}
+
+impl MergeState {
+    fn new(base: AppData, local: AppData, remote: AppData, remote_stamp: Option<FileStamp>) -> Self {
+        let mut changes = vec![(ConflictKey::Board, change(base.header(), local.header(), remote.header()))];
+        if aligned(&base, &local, &remote) {
+            for (cat_idx, b) in base.categories.iter().enumerate() {
+                let c = change(b.header(), local.categories[cat_idx].header(), remote.categories[cat_idx].header());
+                changes.push((ConflictKey::Category(cat_idx), c));
+            }
+        } else {
+            let c = change(category_headers(&base), category_headers(&local), category_headers(&remote));
+            changes.push((ConflictKey::Categories, c));
+        }
+        let ids: BTreeSet<&str> = item_ids(&base).chain(item_ids(&local)).chain(item_ids(&remote)).collect();
+        for id in ids {
+            let c = change(find_item(&base, id), find_item(&local, id), find_item(&remote, id));
+            changes.push((ConflictKey::Item(id.to_string()), c));
+        }
+
+        let auto_merged = changes.iter().filter(|(_, c)| *c == Change::OneSided).count();
+        let conflicts = changes
+            .into_iter()
+            .filter(|(_, c)| *c == Change::Both)
+            .map(|(key, _)| MergeConflict { key, use_remote: false })
+            .collect();
+        Self {
+            base,
+            local,
+            remote,
+            remote_stamp,
+            conflicts,
+            auto_merged,
+        }
+    }
+
+    /// Whether the merge takes the file's version of a part: when only the
+    /// file changed it, or when the user chose so for a conflict.
+    fn takes_remote<T: PartialEq>(&self, key: &ConflictKey, base: T, local: T) -> bool {
+        match self.conflicts.iter().find(|c| c.key == *key) {
+            Some(conflict) => conflict.use_remote,
+            None => local == base,
+        }
+    }
+
+    fn side(&self, remote: bool) -> &AppData {
+        if remote {
+            &self.remote
+        } else {
+            &self.local
+        }
+    }
+
+    /// The same merge against a newer local board, keeping the choices made
+    /// for conflicts that are still there.
+    fn with_local(self, local: AppData) -> Self {
+        let mut refreshed = Self::new(self.base, local, self.remote, self.remote_stamp);
+        for conflict in &mut refreshed.conflicts {
+            if let Some(previous) = self.conflicts.iter().find(|c| c.key == conflict.key) {
+                conflict.use_remote = previous.use_remote;
+            }
+        }
+        refreshed
+    }
+
+    /// Builds the merged board: one-sided changes are taken as they are and
+    /// conflicts follow the user's choice.
+    fn result(&self) -> AppData {
+        let remote = self.takes_remote(&ConflictKey::Board, self.base.header(), self.local.header());
+        let mut merged = self.side(remote).header();
+        // Categories have no ids; when their lists differ, the list taken
+        // decides where items go.
+        let layout = if aligned(&self.base, &self.local, &self.remote) {
+            merged.categories = (0..self.base.categories.len())
+                .map(|cat_idx| {
+                    let key = ConflictKey::Category(cat_idx);
+                    let remote = self.takes_remote(&key, self.base.categories[cat_idx].header(), self.local.categories[cat_idx].header());
+                    self.side(remote).categories[cat_idx].header()
+                })
+                .collect();
+            None
+        } else {
+            let remote = self.takes_remote(&ConflictKey::Categories, category_headers(&self.base), category_headers(&self.local));
+            merged.categories = category_headers(self.side(remote));
+            Some(self.side(remote))
+        };
+
+        let ids: BTreeSet<&str> = item_ids(&self.local).chain(item_ids(&self.remote)).collect();
+        let mut placed: Vec<(usize, Item)> = Vec::new();
+        for id in ids {
+            let key = ConflictKey::Item(id.to_string());
+            let side = self.side(self.takes_remote(&key, find_item(&self.base, id), find_item(&self.local, id)));
+            if let Some((cat_idx, item)) = find_item(side, id) {
+                let target = match layout {
+                    None => cat_idx,
+                    Some(layout) => match find_item(layout, id) {
+                        Some((layout_idx, same)) if same == item => layout_idx,
+                        _ => place_category(&mut merged, &side.categories[cat_idx]),
+                    },
+                };
+                placed.push((target, item.clone()));
+            }
+        }
+
+        placed.sort_by(|(_, a), (_, b)| a.order.cmp(&b.order).then_with(|| a.created_at.cmp(&b.created_at)));
+        for (cat_idx, item) in placed {
+            merged.categories[cat_idx].items.push(item);
+        }
+        for cat in &mut merged.categories {
+            cat.reorder_items();
+        }
+        merged
+    }
+}
+
+/// What one side of a conflict holds, for the dialog.
+fn describe(key: &ConflictKey, data: &AppData, lang: Language) -> String {
+    match key {
+        ConflictKey::Item(id) => match find_item(data, id) {
+            Some((_, item)) => item.title.clone(),
+            None => tr(lang, Msg::MergeDeleted).to_string(),
+        },
+        ConflictKey::Board => tr(lang, Msg::MergeTemplates).to_string(),
+        ConflictKey::Category(cat_idx) => fill(tr(lang, Msg::MergeCategorySettings), &[&data.categories[*cat_idx].name]),
+        ConflictKey::Categories => {
+            let names: Vec<&str> = data.categories.iter().map(|c| c.name.as_str()).collect();
+            fill(tr(lang, Msg::MergeCategoryList), &[&names.join(", ")])
+        }
+    }
+}
+
+/// Index in `merged` of the category matching `cat` by name. A category the
+/// other side removed comes back so the items placed in it aren't lost.
+fn place_category(merged: &mut AppData, cat: &Category) -> usize {
+    if let Some(idx) = merged.categories.iter().position(|c| c.name == cat.name) {
+        return idx;
+    }
+    merged.categories.push(cat.header());
+    merged.categories.len() - 1
+}
+
+impl ProductionManager {
+    fn has_unsaved_changes(&self) -> bool {
+        self.data != *self.base_data
+    }
+
+    /// Remembers the current board as the version that is on disk.
+    pub(crate) fn mark_synced(&mut self) {
+        self.data_revision += 1;
+        self.base_data = Arc::new(self.data.clone());
+        self.disk_stamp = FileStamp::of(&self.data_path);
+        self.saved_generation = self.save_generation;
+        match &self.saver {
+            Some(_) => self.sync_saver(),
+            None => {
+                let saver = Saver::new(&self.egui_ctx, &self.data_path, self.base_data.clone(), self.disk_stamp);
+                self.saver = Some(saver);
+            }
+        }
+    }
+
+    /// Tells the writer what the file holds after the UI re-read it.
+    fn sync_saver(&self) {
+        if let Some(saver) = &self.saver {
+            saver.sync(self.base_data.clone(), self.disk_stamp);
+        }
+    }
+
+    /// Picks up outside changes to the data file. Reloads right away when
+    /// there is nothing to lose, otherwise opens the merge dialog.
+    pub(crate) fn check_external_changes(&mut self) {
+        if self.merge.is_some() || self.save_in_flight() {
+            return;
+        }
+        let stamp = FileStamp::of(&self.data_path);
+        if stamp == self.disk_stamp {
+            return;
+        }
+        let Ok(Some(remote)) = self.storage.load() else {
+            // Missing or half-written; look again on the next check.
+            return;
+        };
+
+        if remote == *self.base_data {
+            self.disk_stamp = stamp;
+            self.sync_saver();
+            // Only the stamp moved; a write skipped as a conflict is still due.
+            if self.has_unsaved_changes() {
+                self.save_data();
+            }
+        } else if !self.has_unsaved_changes() || self.read_only {
+            self.data = remote;
+            self.data.detect_builtin_categories();
+            self.data_revision += 1;
+            self.base_data = Arc::new(self.data.clone());
+            self.disk_stamp = stamp;
+            self.sync_saver();
+            self.show_status(self.t(Msg::ReloadedExternalChanges));
+        } else {
+            let base = (*self.base_data).clone();
+            let local = self.data.clone();
+            let state = MergeState::new(base, local, remote, stamp);
+            if state.conflicts.is_empty() {
+                let result = state.result();
+                self.finish_merge(state, result);
+            } else {
+                self.merge = Some(state);
+            }
+        }
+    }
+
+    fn finish_merge(&mut self, state: MergeState, result: AppData) {
+        self.data = result;
+        self.base_data = Arc::new(state.remote);
+        self.disk_stamp = state.remote_stamp;
+        self.sync_saver();
+        self.save_data();
+        self.show_status(self.t(Msg::MergeDone));
+    }
+
+    pub(crate) fn render_merge_dialog(&mut self, ctx: &egui::Context) {
+        let Some(mut state) = self.merge.take() else {
+            return;
+        };
+        // The board stays editable while the dialog is open; merge what it
+        // holds now, not what it held when the file changed.
+        if state.local != self.data {
+            state = state.with_local(self.data.clone());
+        }
+        if state.conflicts.is_empty() {
+            let result = state.result();
+            self.finish_merge(state, result);
+            return;
+        }
+        let mut result = None;
+
+        egui::Window::new(self.t(Msg::MergeTitle))
+            .id(egui::Id::new("merge_dialog"))
+            .collapsible(false)
+            .resizable(false)
+            .anchor(egui::Align2::CENTER_CENTER, [0.0, 0.0])
+            .show(ctx, |ui| {
+                ui.label(self.t(Msg::MergeExplanation));
+                if state.auto_merged > 0 {
+                    ui.label(
+                        egui::RichText::new(fill(self.t(Msg::MergeAutoMerged), &[&state.auto_merged]))
+                            .color(egui::Color32::from_rgb(150, 150, 155)),
+                    );
+                }
+
+                ui.add_space(8.0);
+
+                let local_label = self.t(Msg::MergeKeepLocal);
+                let remote_label = self.t(Msg::MergeTakeRemote);
+                let lang = self.settings.language;
+                egui::ScrollArea::vertical().max_height(320.0).show(ui, |ui| {
+                    for conflict in &mut state.conflicts {
+                        let local = describe(&conflict.key, &state.local, lang);
+                        let remote = describe(&conflict.key, &state.remote, lang);
+                        egui::Frame::default()
+                            .fill(egui::Color32::from_rgb(55, 55, 60))
+                            .rounding(6.0)
+                            .inner_margin(8.0)
+                            .show(ui, |ui| {
+                                ui.set_width(420.0);
+                                ui.radio_value(&mut conflict.use_remote, false, format!("{}: {}", local_label, local));
+                                ui.radio_value(&mut conflict.use_remote, true, format!("{}: {}", remote_label, remote));
+                            });
+                        ui.add_space(4.0);
+                    }
+                });
+
+                ui.add_space(12.0);
+
+                ui.horizontal(|ui| {
+                    if ui.button(self.t(Msg::MergeUseAllRemote)).clicked() {
+                        result = Some(state.remote.clone());
+                    }
+                    if ui.button(self.t(Msg::MergeUseAllLocal)).clicked() {
+                        result = Some(state.local.clone());
+                    }
+                    ui.add_space(20.0);
+                    if ui.button(self.t(Msg::MergeApply)).clicked() {
+                        result = Some(state.result());
+                    }
+                });
+            });
+
+        match result {
+            Some(result) => self.finish_merge(state, result),
+            None => self.merge = Some(state),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    /// A board with two items in the first category and one in the second.
+    fn base() -> AppData {
+        let mut data = AppData::default();
+        data.categories[0].add_item("A".to_string(), String::new());
+        data.categories[0].add_item("B".to_string(), String::new());
+        data.categories[1].add_item("C".to_string(), String::new());
+        data
+    }
+
+    fn item_mut<'a>(data: &'a mut AppData, title: &str) -> &'a mut Item {
+        data.categories.iter_mut().flat_map(|c| &mut c.items).find(|i| i.title == title).unwrap()
+    }
+
+    fn titles(data: &AppData, cat_idx: usize) -> Vec<&str> {
+        data.categories[cat_idx].items.iter().map(|i| i.title.as_str()).collect()
+    }
+
+    fn merge(base: &AppData, local: &AppData, remote: &AppData) -> MergeState {
+        MergeState::new(base.clone(), local.clone(), remote.clone(), None)
+    }
+
+    #[test]
+    fn one_sided_item_edits_are_both_kept() {
+        let base = base();
+        let mut local = base.clone();
+        item_mut(&mut local, "A").comment = "local".to_string();
+        let mut remote = base.clone();
+        item_mut(&mut remote, "C").comment = "remote".to_string();
+
+        let state = merge(&base, &local, &remote);
+        assert!(state.conflicts.is_empty());
+        assert_eq!(state.auto_merged, 2);
+        let mut result = state.result();
+        assert_eq!(item_mut(&mut result, "A").comment, "local");
+        assert_eq!(item_mut(&mut result, "C").comment, "remote");
+        assert_eq!(titles(&result, 0), ["A", "B"]);
+    }
+
+    #[test]
+    fn item_edited_on_both_sides_follows_the_choice() {
+        let base = base();
+        let mut local = base.clone();
+        item_mut(&mut local, "A").comment = "local".to_string();
+        let mut remote = base.clone();
+        item_mut(&mut remote, "A").comment = "remote".to_string();
+
+        let mut state = merge(&base, &local, &remote);
+        assert_eq!(state.conflicts.len(), 1);
+        assert_eq!(item_mut(&mut state.result(), "A").comment, "local");
+        state.conflicts[0].use_remote = true;
+        assert_eq!(item_mut(&mut state.result(), "A").comment, "remote");
+    }
+
+    #[test]
+    fn delete_against_edit_is_a_conflict() {
+        let base = base();
+        let mut local = base.clone();
+        let id = item_mut(&mut local, "B").id.clone();
+        local.categories[0].remove_item(&id);
+        let mut remote = base.clone();
+        item_mut(&mut remote, "B").comment = "remote".to_string();
+
+        let mut state = merge(&base, &local, &remote);
+        assert_eq!(state.conflicts.len(), 1);
+        assert_eq!(titles(&state.result(), 0), ["A"]);
+        state.conflicts[0].use_remote = true;
+        let mut result = state.result();
+        assert_eq!(titles(&result, 0), ["A", "B"]);
+        assert_eq!(item_mut(&mut result, "B").comment, "remote");
+    }
+
+    #[test]
+    fn category_settings_changed_on_different_sides_are_both_kept() {
+        let base = base();
+        let mut local = base.clone();
+        local.categories[0].name = "Renamed".to_string();
+        let mut remote = base.clone();
+        remote.categories[1].color = Some([1, 2, 3]);
+        item_mut(&mut remote, "C").comment = "remote".to_string();
+
+        let state = merge(&base, &local, &remote);
+        assert!(state.conflicts.is_empty());
+        let mut result = state.result();
+        assert_eq!(result.categories[0].name, "Renamed");
+        assert_eq!(result.categories[1].color, Some([1, 2, 3]));
+        assert_eq!(item_mut(&mut result, "C").comment, "remote");
+    }
+
+    #[test]
+    fn category_settings_changed_on_both_sides_are_a_conflict() {
+        let base = base();
+        let mut local = base.clone();
+        local.categories[0].name = "Local".to_string();
+        let mut remote = base.clone();
+        remote.categories[0].name = "Remote".to_string();
+
+        let mut state = merge(&base, &local, &remote);
+        assert_eq!(state.conflicts.len(), 1);
+        assert_eq!(state.conflicts[0].key, ConflictKey::Category(0));
+        assert_eq!(state.result().categories[0].name, "Local");
+        state.conflicts[0].use_remote = true;
+        let result = state.result();
+        assert_eq!(result.categories[0].name, "Remote");
+        assert_eq!(titles(&result, 0), ["A", "B"]);
+    }
+
+    #[test]
+    fn items_follow_their_category_when_the_other_side_removed_one() {
+        let base = base();
+        let mut local = base.clone();
+        local.categories[1].add_item("D".to_string(), String::new());
+        let mut remote = base.clone();
+        let removed = remote.categories.remove(0);
+        let c = remote.categories[0].name.clone();
+
+        let state = merge(&base, &local, &remote);
+        // Removing a category drops its items on that side, which conflicts
+        // with nothing local.
+        assert!(state.conflicts.is_empty());
+        let result = state.result();
+        assert!(result.categories.iter().all(|cat| cat.name != removed.name));
+        assert_eq!(result.categories[0].name, c);
+        assert_eq!(titles(&result, 0), ["C", "D"]);
+    }
+
+    #[test]
+    fn category_lists_changed_on_both_sides_are_a_conflict() {
+        let base = base();
+        let mut local = base.clone();
+        local.categories.push(Category::new("Local"));
+        let mut remote = base.clone();
+        remote.categories[0].name = "Remote".to_string();
+
+        let mut state = merge(&base, &local, &remote);
+        assert_eq!(state.conflicts.len(), 1);
+        assert_eq!(state.conflicts[0].key, ConflictKey::Categories);
+        assert_eq!(state.result().categories.last().unwrap().name, "Local");
+        state.conflicts[0].use_remote = true;
+        let result = state.result();
+        assert_eq!(result.categories[0].name, "Remote");
+        assert_eq!(titles(&result, 0), ["A", "B"]);
+    }
+}
@@ -0,0 +1,291 @@
+//! Repeating items and due date reminders. Completing a repeating item
+//! creates its next occurrence, which takes over the rule.
+
+use crate::i18n::{fill, tr, Language, Msg};
+use crate::status::{today, ItemStatus};
+use crate::{CardAction, Item, ProductionManager};
+use chrono::{Months, NaiveDate};
+use eframe::egui;
+use serde::{Deserialize, Serialize};
+
+#[derive(Serialize, Deserialize, Clone, Copy, Debug, PartialEq, Eq, Default)]
+pub enum RecurrenceUnit {
+    Days,
+    Weeks,
+    #[default]
+    Months,
+    Years,
+}
+
+impl RecurrenceUnit {
+    const ALL: [RecurrenceUnit; 4] = [RecurrenceUnit::Days, RecurrenceUnit::Weeks, RecurrenceUnit::Months, RecurrenceUnit::Years];
+
+    fn name(self, lang: Language) -> &'static str {
+        let msg = match self {
+            RecurrenceUnit::Days => Msg::RecurUnitDays,
+            RecurrenceUnit::Weeks => Msg::RecurUnitWeeks,
+            RecurrenceUnit::Months => Msg::RecurUnitMonths,
+            RecurrenceUnit::Years => Msg::RecurUnitYears,
+        };
+        tr(lang, msg)
+    }
+
+    /// Name of the rule that repeats every single unit.
+    fn preset(self) -> Msg {
+        match self {
+            RecurrenceUnit::Days => Msg::RecurDaily,
+            RecurrenceUnit::Weeks => Msg::RecurWeekly,
+            RecurrenceUnit::Months => Msg::RecurMonthly,
+            RecurrenceUnit::Years => Msg::RecurYearly,
+        }
+    }
+
+    /// Name of the rule that repeats every `{}` units.
+    fn every(self) -> Msg {
+        match self {
+            RecurrenceUnit::Days => Msg::RecurEveryDays,
+            RecurrenceUnit::Weeks => Msg::RecurEveryWeeks,
+            RecurrenceUnit::Months => Msg::RecurEveryMonths,
+            RecurrenceUnit::Years => Msg::RecurEveryYears,
+        }
+    }
+}
+
+#[derive(Serialize, Deserialize, Clone, Copy, Debug, PartialEq, Eq)]
+pub struct Recurrence {
+    pub every: u32,
+    pub unit: RecurrenceUnit,
+}
+
+impl Recurrence {
+    pub fn name(self, lang: Language) -> String {
+        if self.every == 1 {
+            tr(lang, self.unit.preset()).to_string()
+        } else {
+            fill(tr(lang, self.unit.every()), &[&self.every])
+        }
+    }
+
+    /// Reads a name written by [`Recurrence::name`] in any language.
+    pub fn parse(text: &str) -> Option<Self> {
+        let text = text.trim();
+        for lang in Language::ALL {
+            for unit in RecurrenceUnit::ALL {
+                if text == tr(lang, unit.preset()) {
+                    return Some(Self { every: 1, unit });
+                }
+                let Some((prefix, suffix)) = tr(lang, unit.every()).split_once("{}") else {
+                    continue;
+                };
+                let every = text.strip_prefix(prefix).and_then(|t| t.strip_suffix(suffix)).and_then(|n| n.trim().parse().ok());
+                if let Some(every) = every.filter(|&n| n > 0) {
+                    return Some(Self { every, unit });
+                }
+            }
+        }
+        None
+    }
+
+    /// `date` moved forward by `steps` repetitions.
+    fn advance(self, date: NaiveDate, steps: u32) -> NaiveDate {
+        let n = self.every.max(1) * steps;
+        let next = match self.unit {
+            RecurrenceUnit::Days => date.checked_add_days(chrono::Days::new(n.into())),
+            RecurrenceUnit::Weeks => date.checked_add_days(chrono::Days::new(u64::from(n) * 7)),
+            RecurrenceUnit::Months => date.checked_add_months(Months::new(n)),
+            RecurrenceUnit::Years => date.checked_add_months(Months::new(n * 12)),
+        };
+        next.unwrap_or(date)
+    }
+}
+
+/// The item that follows `item` under `rule`. Its due date is the first
+/// repetition after today, so finishing late doesn't create an item that is
+/// already overdue; the schedule moves by the same number of repetitions.
+fn next_occurrence(item: &Item, rule: Recurrence, today: NaiveDate) -> Item {
+    let anchor = item.due.unwrap_or(today);
+    let mut steps = 1;
+    while rule.advance(anchor, steps) <= today && steps < 10_000 {
+        steps += 1;
+    }
+    let mut next = item.clone();
+    next.id = uuid::Uuid::new_v4().to_string();
+    next.created_at = chrono::Local::now().format("%Y-%m-%d %H:%M").to_string();
+    next.updated_at = None;
+    next.status = ItemStatus::Todo;
+    next.completed_at = None;
+    next.due = Some(rule.advance(anchor, steps));
+    next.start_date = item.start_date.map(|d| rule.advance(d, steps));
+    next.end_date = item.end_date.map(|d| rule.advance(d, steps));
+    next.time_entries.clear();
+    next.relations.clear();
+    next.attachments.clear();
+    for entry in &mut next.checklist {
+        entry.done = false;
+    }
+    next.recurrence = Some(rule);
+    next
+}
+
+/// `🔁 Monthly` on a card.
+pub fn render_badge(ui: &mut egui::Ui, lang: Language, item: &Item) {
+    if let Some(rule) = item.recurrence {
+        ui.label(egui::RichText::new(format!("🔁 {}", rule.name(lang))).size(12.0).color(egui::Color32::from_rgb(150, 150, 155)))
+            .on_hover_text(tr(lang, Msg::RecurrenceHint));
+    }
+}
+
+/// Repeat selector for the edit popup.
+pub fn render_recurrence_editor(ui: &mut egui::Ui, lang: Language, rule: &mut Option<Recurrence>) {
+    ui.horizontal(|ui| {
+        ui.label(tr(lang, Msg::RecurrenceLabel));
+        let custom = rule.is_some_and(|r| r.every != 1);
+        let selected = match rule {
+            None => tr(lang, Msg::RecurNone).to_string(),
+            Some(_) if custom => tr(lang, Msg::RecurCustom).to_string(),
+            Some(r) => r.name(lang),
+        };
+        egui::ComboBox::from_id_salt("recurrence_select")
+            .selected_text(selected)
+            .show_ui(ui, |ui| {
+                if ui.selectable_label(rule.is_none(), tr(lang, Msg::RecurNone)).clicked() {
+                    *rule = None;
+                }
+                for unit in RecurrenceUnit::ALL {
+                    let preset = Recurrence { every: 1, unit };
+                    if ui.selectable_label(*rule == Some(preset), tr(lang, unit.preset())).clicked() {
+                        *rule = Some(preset);
+                    }
+                }
+                if ui.selectable_label(custom, tr(lang, Msg::RecurCustom)).clicked() && !custom {
+                    let unit = rule.map_or(RecurrenceUnit::default(), |r| r.unit);
+                    *rule = Some(Recurrence { every: 2, unit });
+                }
+            });
+        if let Some(r) = rule.as_mut().filter(|_| custom) {
+            ui.label(tr(lang, Msg::RecurEvery));
+            ui.add(egui::DragValue::new(&mut r.every).range(2..=365));
+            egui::ComboBox::from_id_salt("recurrence_unit")
+                .selected_text(r.unit.name(lang))
+                .show_ui(ui, |ui| {
+                    for unit in RecurrenceUnit::ALL {
+                        ui.selectable_value(&mut r.unit, unit, unit.name(lang));
+                    }
+                });
+        }
+    });
+}
+
+impl ProductionManager {
+    /// Adds the next occurrence of a repeating item that was just completed,
+    /// right below it.
+    pub(crate) fn repeat_item(&mut self, item_id: &str) {
+        let today = today();
+        let Some((cat, item_idx)) = self
+            .data
+            .categories
+            .iter_mut()
+            .find_map(|c| c.items.iter().position(|i| i.id == item_id).map(|idx| (c, idx)))
+        else {
+            return;
+        };
+        let Some(rule) = cat.items[item_idx].recurrence.take() else {
+            return;
+        };
+        let next = next_occurrence(&cat.items[item_idx], rule, today);
+        let due = next.due.map(|d| d.format("%Y-%m-%d").to_string()).unwrap_or_default();
+        cat.items.insert(item_idx + 1, next);
+        cat.reorder_items();
+        self.show_status(&fill(self.t(Msg::NextOccurrenceCreated), &[&due]));
+    }
+
+    /// Open items due today or earlier that haven't been dismissed, earliest
+    /// first.
+    fn reminders(&self) -> Vec<(usize, usize)> {
+        let today = today();
+        let mut due: Vec<(usize, usize)> = self
+            .data
+            .categories
+            .iter()
+            .enumerate()
+            .flat_map(|(cat_idx, cat)| cat.items.iter().enumerate().map(move |(item_idx, item)| (cat_idx, item_idx, item)))
+            .filter(|(_, _, item)| item.status != ItemStatus::Done && item.due.is_some_and(|d| d <= today))
+            .filter(|(_, _, item)| !self.dismissed_reminders.contains(&item.id))
+            .map(|(cat_idx, item_idx, _)| (cat_idx, item_idx))
+            .collect();
+        due.sort_by_key(|&(c, i)| self.data.categories[c].items[i].due);
+        due
+    }
+
+    /// Remembers when the app was last started and announces what came due
+    /// since then.
+    pub(crate) fn note_app_opened(&mut self) {
+        let today = today();
+        let previous = self.previous_open;
+        let missed = self
+            .data
+            .categories
+            .iter()
+            .flat_map(|c| &c.items)
+            .filter(|i| i.status != ItemStatus::Done && i.due.is_some_and(|d| d <= today && previous.is_none_or(|p| d > p)))
+            .count();
+        if previous.is_some() && missed > 0 {
+            self.show_status(&fill(self.t(Msg::RemindersSinceLastOpen), &[&missed]));
+        }
+        if self.settings.last_opened != Some(today) {
+            self.settings.last_opened = Some(today);
+            self.save_settings();
+        }
+    }
+
+    /// Bell in the header listing due items; clicking one opens it.
+    pub(crate) fn render_reminders(&mut self, ui: &mut egui::Ui) {
+        let reminders = self.reminders();
+        if reminders.is_empty() {
+            return;
+        }
+        let lang = self.settings.language;
+        let today = today();
+        let mut open = None;
+        let mut dismiss = false;
+        let label = egui::RichText::new(format!("🔔 {}", reminders.len())).color(egui::Color32::from_rgb(230, 190, 90));
+        ui.menu_button(label, |ui| {
+            ui.label(egui::RichText::new(tr(lang, Msg::RemindersTitle)).strong());
+            ui.separator();
+            for &(cat_idx, item_idx) in &reminders {
+                let cat = &self.data.categories[cat_idx];
+                let item = &cat.items[item_idx];
+                let Some(due) = item.due else { continue };
+                let (when, color) = if due == today {
+                    (tr(lang, Msg::ReminderDueToday).to_string(), egui::Color32::from_rgb(230, 190, 90))
+                } else {
+                    (due.format("%Y-%m-%d").to_string(), egui::Color32::from_rgb(220, 100, 100))
+                };
+                ui.horizontal(|ui| {
+                    ui.label(egui::RichText::new("■").color(cat.color(cat_idx)));
+                    if ui.button(&item.title).clicked() {
+                        open = Some((cat_idx, item_idx));
+                        ui.close_menu();
+                    }
+                    ui.label(egui::RichText::new(when).size(13.0).color(color));
+                    if self.previous_open.is_some_and(|p| due > p) && due < today {
+                        ui.label(egui::RichText::new(tr(lang, Msg::ReminderWhileAway)).size(12.0).color(egui::Color32::from_rgb(150, 150, 155)));
+                    }
+                });
+            }
+            ui.separator();
+            if ui.button(tr(lang, Msg::RemindersDismiss)).clicked() {
+                dismiss = true;
+                ui.close_menu();
+            }
+        });
+        if let Some((cat_idx, item_idx)) = open {
+            self.apply_card_action(cat_idx, item_idx, CardAction::Edit);
+        }
+        if dismiss {
+            for (cat_idx, item_idx) in reminders {
+                self.dismissed_reminders.insert(self.data.categories[cat_idx].items[item_idx].id.clone());
+            }
+        }
+    }
+}
@@ -0,0 +1,436 @@
+//! Typed relationships between items across categories, the blocked badge,
+//! the warning when finishing a blocked item and the dependency graph view.
+//!
+//! A relation is stored once, on the item it was added to; the other side is
+//! derived, so "A blocks B" on A and "B blocked by A" on B mean the same.
+
+use crate::i18n::{tr, Language, Msg};
+use crate::status::ItemStatus;
+use crate::{AppData, CardAction, Item, ProductionManager};
+use eframe::egui;
+use serde::{Deserialize, Serialize};
+use std::collections::HashMap;
+
+const NODE_WIDTH: f32 = 180.0;
+const NODE_HEIGHT: f32 = 40.0;
+const LAYER_GAP: f32 = 80.0;
+const NODE_GAP: f32 = 16.0;
+
+#[derive(Serialize, Deserialize, Clone, Copy, Debug, PartialEq, Eq)]
+pub enum RelationKind {
+    Blocks,
+    BlockedBy,
+    Related,
+}
+
+impl RelationKind {
+    const ALL: [RelationKind; 3] = [RelationKind::Blocks, RelationKind::BlockedBy, RelationKind::Related];
+
+    pub fn name(self, lang: Language) -> &'static str {
+        let msg = match self {
+            RelationKind::Blocks => Msg::RelationBlocks,
+            RelationKind::BlockedBy => Msg::RelationBlockedBy,
+            RelationKind::Related => Msg::RelationRelated,
+        };
+        tr(lang, msg)
+    }
+
+    /// The same relation seen from the other item.
+    fn inverse(self) -> Self {
+        match self {
+            RelationKind::Blocks => RelationKind::BlockedBy,
+            RelationKind::BlockedBy => RelationKind::Blocks,
+            RelationKind::Related => RelationKind::Related,
+        }
+    }
+}
+
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq)]
+pub struct Relation {
+    pub kind: RelationKind,
+    /// `Item::id` of the other item.
+    pub target: String,
+}
+
+/// An item marked done while it still had open blockers, waiting for the
+/// user to confirm.
+pub struct PendingDone {
+    item_id: String,
+    blockers: Vec<String>,
+}
+
+/// Item lookups and relations in both directions, built once so views that
+/// ask about many items don't rescan the board for each.
+pub struct RelationIndex<'a> {
+    data: &'a AppData,
+    /// `(category, item)` indices by item id.
+    positions: HashMap<&'a str, (usize, usize)>,
+    /// Relations other items hold towards an item, seen from that item.
+    incoming: HashMap<&'a str, Vec<(RelationKind, &'a Item)>>,
+}
+
+impl<'a> RelationIndex<'a> {
+    pub fn new(data: &'a AppData) -> Self {
+        let mut positions = HashMap::new();
+        let mut incoming: HashMap<&str, Vec<(RelationKind, &Item)>> = HashMap::new();
+        for (cat_idx, cat) in data.categories.iter().enumerate() {
+            for (item_idx, item) in cat.items.iter().enumerate() {
+                positions.insert(item.id.as_str(), (cat_idx, item_idx));
+                for relation in item.relations.iter().filter(|r| r.target != item.id) {
+                    incoming.entry(relation.target.as_str()).or_default().push((relation.kind.inverse(), item));
+                }
+            }
+        }
+        Self { data, positions, incoming }
+    }
+
+    pub fn find(&self, id: &str) -> Option<(usize, &'a Item)> {
+        let &(cat_idx, item_idx) = self.positions.get(id)?;
+        Some((cat_idx, &self.data.categories[cat_idx].items[item_idx]))
+    }
+
+    /// Every relation of `id` as `(kind, other item)`, both the ones stored
+    /// on it and the ones other items point at it with.
+    pub fn relations_of(&self, id: &str) -> Vec<(RelationKind, &'a Item)> {
+        let mut relations = Vec::new();
+        if let Some((_, item)) = self.find(id) {
+            for relation in &item.relations {
+                if let Some((_, other)) = self.find(&relation.target) {
+                    relations.push((relation.kind, other));
+                }
+            }
+        }
+        relations.extend(self.incoming.get(id).into_iter().flatten().copied());
+        relations
+    }
+
+    /// Items blocking `id` that aren't done yet.
+    pub fn open_blockers(&self, id: &str) -> Vec<&'a Item> {
+        let mut blockers: Vec<&Item> = Vec::new();
+        for (kind, other) in self.relations_of(id) {
+            if kind == RelationKind::BlockedBy && other.status != ItemStatus::Done && !blockers.iter().any(|b| b.id == other.id) {
+                blockers.push(other);
+            }
+        }
+        blockers
+    }
+
+    /// Titles of open blockers for every blocked item, for the card badge.
+    pub fn blocked_items(&self) -> HashMap<String, Vec<String>> {
+        let mut blocked: HashMap<String, Vec<String>> = HashMap::new();
+        let open = |id: &str| self.find(id).filter(|(_, i)| i.status != ItemStatus::Done).map(|(_, i)| i);
+        for item in self.data.categories.iter().flat_map(|c| &c.items) {
+            for relation in &item.relations {
+                let (blocked_id, blocker) = match relation.kind {
+                    RelationKind::BlockedBy => (item.id.as_str(), open(&relation.target)),
+                    RelationKind::Blocks if item.status != ItemStatus::Done => (relation.target.as_str(), Some(item)),
+                    _ => continue,
+                };
+                if let Some(blocker) = blocker {
+                    let titles = blocked.entry(blocked_id.to_string()).or_default();
+                    if !titles.contains(&blocker.title) {
+                        titles.push(blocker.title.clone());
+                    }
+                }
+            }
+        }
+        blocked
+    }
+}
+
+impl AppData {
+    pub fn find_item(&self, id: &str) -> Option<(usize, &Item)> {
+        self.categories
+            .iter()
+            .enumerate()
+            .find_map(|(cat_idx, cat)| cat.items.iter().find(|i| i.id == id).map(|item| (cat_idx, item)))
+    }
+
+    /// Items blocking `id` that aren't done yet.
+    pub fn open_blockers(&self, id: &str) -> Vec<&Item> {
+        RelationIndex::new(self).open_blockers(id)
+    }
+
+    /// Titles of open blockers for every blocked item, for the card badge.
+    pub fn blocked_items(&self) -> HashMap<String, Vec<String>> {
+        RelationIndex::new(self).blocked_items()
+    }
+
+    /// Drops relations pointing at a deleted item.
+    pub fn forget_item(&mut self, id: &str) {
+        for item in self.categories.iter_mut().flat_map(|c| &mut c.items) {
+            item.relations.retain(|r| r.target != id);
+        }
+    }
+}
+
+/// "Blocked" badge on a card, listing the blockers on hover.
+pub fn render_blocked_badge(ui: &mut egui::Ui, lang: Language, blockers: &[String]) {
+    let text = egui::RichText::new(format!("⛔ {}", tr(lang, Msg::Blocked)))
+        .size(12.0)
+        .color(egui::Color32::WHITE)
+        .background_color(egui::Color32::from_rgb(170, 70, 70));
+    ui.label(text).on_hover_text(format!("{}\n{}", tr(lang, Msg::BlockedByHint), blockers.join("\n")));
+}
+
+/// Relation rows for the edit popup. Relations other items hold towards
+/// this one are listed read-only below them.
+pub fn render_relation_editor(
+    ui: &mut egui::Ui,
+    lang: Language,
+    data: &AppData,
+    item_id: &str,
+    relations: &mut Vec<Relation>,
+) {
+    let index = RelationIndex::new(data);
+    let label = |id: &str| match index.find(id) {
+        Some((cat_idx, item)) => format!("{} / {}", data.categories[cat_idx].name, item.title),
+        None => "?".to_string(),
+    };
+    let mut remove = None;
+    for (idx, relation) in relations.iter_mut().enumerate() {
+        ui.horizontal(|ui| {
+            egui::ComboBox::from_id_salt(("relation_kind", idx))
+                .selected_text(relation.kind.name(lang))
+                .show_ui(ui, |ui| {
+                    for kind in RelationKind::ALL {
+                        ui.selectable_value(&mut relation.kind, kind, kind.name(lang));
+                    }
+                });
+            egui::ComboBox::from_id_salt(("relation_target", idx))
+                .width(220.0)
+                .selected_text(label(&relation.target))
+                .show_ui(ui, |ui| {
+                    for cat in &data.categories {
+                        for item in cat.items.iter().filter(|i| i.id != item_id) {
+                            ui.selectable_value(&mut relation.target, item.id.clone(), format!("{} / {}", cat.name, item.title));
+                        }
+                    }
+                });
+            if ui.small_button("✖").on_hover_text(tr(lang, Msg::Delete)).clicked() {
+                remove = Some(idx);
+            }
+        });
+    }
+    if let Some(idx) = remove {
+        relations.remove(idx);
+    }
+
+    let first_other = data.categories.iter().flat_map(|c| &c.items).find(|i| i.id != item_id);
+    if let Some(other) = first_other {
+        if ui.small_button(tr(lang, Msg::AddRelation)).clicked() {
+            relations.push(Relation {
+                kind: RelationKind::BlockedBy,
+                target: other.id.clone(),
+            });
+        }
+    }
+
+    for (kind, item) in index.incoming.get(item_id).into_iter().flatten() {
+        ui.label(
+            egui::RichText::new(format!("{} {}  ({})", kind.name(lang), label(&item.id), tr(lang, Msg::RelationIncoming)))
+                .size(13.0)
+                .color(egui::Color32::from_rgb(150, 150, 155)),
+        );
+    }
+}
+
+/// Longest chain of blockers above each related item, cycles cut off.
+fn layers(index: &RelationIndex, ids: &[&str]) -> HashMap<String, usize> {
+    let mut depth: HashMap<String, usize> = ids.iter().map(|id| (id.to_string(), 0)).collect();
+    for _ in 0..ids.len() {
+        let mut changed = false;
+        for id in ids {
+            for (kind, other) in index.relations_of(id) {
+                if kind != RelationKind::BlockedBy {
+                    continue;
+                }
+                let above = depth.get(&other.id).copied().unwrap_or(0) + 1;
+                if above > depth[*id] && above < ids.len() {
+                    depth.insert(id.to_string(), above);
+                    changed = true;
+                }
+            }
+        }
+        if !changed {
+            break;
+        }
+    }
+    depth
+}
+
+fn arrow(painter: &egui::Painter, from: egui::Pos2, to: egui::Pos2, stroke: egui::Stroke) {
+    painter.line_segment([from, to], stroke);
+    let dir = (to - from).normalized();
+    let side = egui::vec2(-dir.y, dir.x);
+    let tip = [to, to - dir * 9.0 + side * 4.5, to - dir * 9.0 - side * 4.5];
+    painter.add(egui::Shape::convex_polygon(tip.to_vec(), stroke.color, egui::Stroke::NONE));
+}
+
+impl ProductionManager {
+    /// Changes an item's status, asking first when it would close an item
+    /// that still has open blockers.
+    pub(crate) fn request_status(&mut self, cat_idx: usize, item_idx: usize, status: ItemStatus) {
+        let item = &self.data.categories[cat_idx].items[item_idx];
+        if item.status == status {
+            return;
+        }
+        if status == ItemStatus::Done {
+            let blockers: Vec<String> = self.data.open_blockers(&item.id).iter().map(|b| b.title.clone()).collect();
+            if !blockers.is_empty() {
+                self.pending_done = Some(PendingDone {
+                    item_id: item.id.clone(),
+                    blockers,
+                });
+                return;
+            }
+        }
+        let item = &mut self.data.categories[cat_idx].items[item_idx];
+        item.set_status(status);
+        item.touch();
+        if status == ItemStatus::Done {
+            let id = item.id.clone();
+            self.repeat_item(&id);
+        }
+        self.save_data();
+    }
+
+    pub(crate) fn render_blocked_warning(&mut self, ctx: &egui::Context) {
+        let Some(pending) = &self.pending_done else {
+            return;
+        };
+        let title = self.data.find_item(&pending.item_id).map(|(_, i)| i.title.clone()).unwrap_or_default();
+        let mut close = false;
+        let mut confirm = false;
+        egui::Window::new(self.t(Msg::BlockedWarningTitle))
+            .id(egui::Id::new("blocked_warning"))
+            .collapsible(false)
+            .resizable(false)
+            .anchor(egui::Align2::CENTER_CENTER, [0.0, 0.0])
+            .show(ctx, |ui| {
+                ui.label(crate::i18n::fill(self.t(Msg::BlockedWarning), &[&title]));
+                ui.add_space(4.0);
+                for blocker in &pending.blockers {
+                    ui.label(format!("⛔ {}", blocker));
+                }
+                ui.add_space(12.0);
+                ui.horizontal(|ui| {
+                    if ui.button(self.t(Msg::Cancel)).clicked() {
+                        close = true;
+                    }
+                    ui.add_space(20.0);
+                    if ui.button(self.t(Msg::MarkDoneAnyway)).clicked() {
+                        confirm = true;
+                    }
+                });
+            });
+        if confirm {
+            let id = pending.item_id.clone();
+            if let Some(item) = self.data.categories.iter_mut().flat_map(|c| &mut c.items).find(|i| i.id == id) {
+                item.set_status(ItemStatus::Done);
+                item.touch();
+            }
+            self.repeat_item(&id);
+            self.save_data();
+        }
+        if close || confirm {
+            self.pending_done = None;
+        }
+    }
+
+    /// Layered graph of every item with a relation: blockers to the left of
+    /// what they block, related items joined by dashed lines.
+    pub(crate) fn render_graph(&mut self, ui: &mut egui::Ui) {
+        let lang = self.settings.language;
+        ui.label(egui::RichText::new(self.t(Msg::GraphHint)).size(13.0).color(egui::Color32::from_rgb(150, 150, 155)));
+        ui.add_space(6.0);
+
+        let data = &self.data;
+        let index = RelationIndex::new(data);
+        let ids: Vec<&str> = data
+            .categories
+            .iter()
+            .flat_map(|c| &c.items)
+            .filter(|i| !index.relations_of(&i.id).is_empty())
+            .map(|i| i.id.as_str())
+            .collect();
+        if ids.is_empty() {
+            ui.centered_and_justified(|ui| {
+                ui.label(egui::RichText::new(tr(lang, Msg::GraphEmpty)).color(egui::Color32::from_rgb(150, 150, 155)));
+            });
+            return;
+        }
+
+        let depth = layers(&index, &ids);
+        let mut columns: Vec<Vec<&str>> = vec![Vec::new(); depth.values().max().map_or(1, |d| d + 1)];
+        for id in &ids {
+            columns[depth[*id]].push(id);
+        }
+        let tallest = columns.iter().map(Vec::len).max().unwrap_or(1) as f32;
+        let size = egui::vec2(
+            columns.len() as f32 * (NODE_WIDTH + LAYER_GAP),
+            tallest * (NODE_HEIGHT + NODE_GAP) + NODE_GAP,
+        );
+
+        let mut open = None;
+        egui::ScrollArea::both().id_salt("relation_graph").auto_shrink([false, false]).show(ui, |ui| {
+            let (rect, _) = ui.allocate_exact_size(size, egui::Sense::hover());
+            let painter = ui.painter_at(rect);
+            let mut nodes: HashMap<&str, egui::Rect> = HashMap::new();
+            for (col, ids) in columns.iter().enumerate() {
+                for (row, id) in ids.iter().enumerate() {
+                    let min = rect.min + egui::vec2(col as f32 * (NODE_WIDTH + LAYER_GAP), NODE_GAP + row as f32 * (NODE_HEIGHT + NODE_GAP));
+                    nodes.insert(id, egui::Rect::from_min_size(min, egui::vec2(NODE_WIDTH, NODE_HEIGHT)));
+                }
+            }
+
+            let block_stroke = egui::Stroke::new(1.5, egui::Color32::from_rgb(200, 110, 110));
+            let related_stroke = egui::Stroke::new(1.0, egui::Color32::from_rgb(120, 120, 130));
+            for id in &ids {
+                let Some((_, item)) = index.find(id) else { continue };
+                for relation in &item.relations {
+                    let (Some(&a), Some(&b)) = (nodes.get(*id), nodes.get(relation.target.as_str())) else {
+                        continue;
+                    };
+                    match relation.kind {
+                        RelationKind::Blocks => arrow(&painter, a.right_center(), b.left_center(), block_stroke),
+                        RelationKind::BlockedBy => arrow(&painter, b.right_center(), a.left_center(), block_stroke),
+                        RelationKind::Related => {
+                            painter.extend(egui::Shape::dashed_line(&[a.center(), b.center()], related_stroke, 6.0, 4.0));
+                        }
+                    }
+                }
+            }
+
+            for id in &ids {
+                let Some(&(cat_idx, item_idx)) = index.positions.get(id) else { continue };
+                let item = &data.categories[cat_idx].items[item_idx];
+                let node = nodes[*id];
+                let response = ui.interact(node, ui.id().with(("graph_node", *id)), egui::Sense::click());
+                let fill = if response.hovered() {
+                    egui::Color32::from_rgb(70, 70, 78)
+                } else {
+                    egui::Color32::from_rgb(55, 55, 60)
+                };
+                painter.rect(node, 6.0, fill, egui::Stroke::new(1.5, item.status.color()));
+                painter.rect_filled(
+                    egui::Rect::from_min_size(node.min, egui::vec2(5.0, NODE_HEIGHT)),
+                    egui::Rounding { nw: 6.0, sw: 6.0, ..Default::default() },
+                    data.categories[cat_idx].color(cat_idx),
+                );
+                let blocked = !index.open_blockers(id).is_empty();
+                let text = if blocked { format!("⛔ {}", item.title) } else { item.title.clone() };
+                let galley = painter.layout(text, egui::FontId::proportional(13.0), egui::Color32::from_rgb(220, 220, 225), NODE_WIDTH - 16.0);
+                painter.galley(egui::pos2(node.left() + 10.0, node.center().y - galley.size().y / 2.0), galley, egui::Color32::WHITE);
+                let response = response.on_hover_text(format!("{}\n{}", data.categories[cat_idx].name, item.status.name(lang)));
+                if response.clicked() {
+                    open = Some((cat_idx, item_idx));
+                }
+            }
+        });
+
+        if let Some((cat_idx, item_idx)) = open {
+            self.apply_card_action(cat_idx, item_idx, CardAction::Edit);
+        }
+    }
+}
@@ -0,0 +1,168 @@
+//! Release history of an item: versions with a date and notes, shown as a
+//! badge on the card and exported as a Keep a Changelog file.
+
+use crate::i18n::{fill, tr, Language, Msg};
+use eframe::egui;
+use serde::{Deserialize, Serialize};
+
+#[derive(Serialize, Deserialize, Clone, Debug, Default, PartialEq)]
+pub struct Release {
+    pub version: String,
+    /// `YYYY-MM-DD`.
+    #[serde(default)]
+    pub date: String,
+    #[serde(default)]
+    pub notes: String,
+}
+
+/// Version with a `v` in front unless it already has a prefix.
+fn display_version(version: &str) -> String {
+    if version.starts_with(|c: char| c.is_ascii_digit()) {
+        format!("v{}", version)
+    } else {
+        version.to_string()
+    }
+}
+
+/// Suggestion for the release after `latest`: the last number bumped.
+fn next_version(latest: Option<&Release>) -> String {
+    let Some(latest) = latest else {
+        return "1.0.0".to_string();
+    };
+    let version = latest.version.trim();
+    let digits_end = version.rfind(|c: char| c.is_ascii_digit()).map_or(0, |i| i + 1);
+    let digits_start = version[..digits_end]
+        .char_indices()
+        .rev()
+        .find(|(_, c)| !c.is_ascii_digit())
+        .map_or(0, |(i, c)| i + c.len_utf8());
+    match version[digits_start..digits_end].parse::<u64>() {
+        Ok(n) => format!("{}{}{}", &version[..digits_start], n + 1, &version[digits_end..]),
+        Err(_) => String::new(),
+    }
+}
+
+/// Drops blank entries and orders the rest newest first. Releases on the
+/// same day keep the order they were entered in.
+pub fn normalized(releases: &[Release]) -> Vec<Release> {
+    let mut releases: Vec<Release> = releases
+        .iter()
+        .map(|r| Release {
+            version: r.version.trim().to_string(),
+            date: r.date.trim().to_string(),
+            notes: r.notes.trim().to_string(),
+        })
+        .filter(|r| !r.version.is_empty())
+        .collect();
+    releases.sort_by(|a, b| b.date.cmp(&a.date));
+    releases
+}
+
+/// Release history section for the category Markdown export.
+pub fn to_markdown(releases: &[Release], lang: Language) -> String {
+    if releases.is_empty() {
+        return String::new();
+    }
+    let mut md = format!("### {}\n\n", tr(lang, Msg::MarkdownReleases));
+    for release in releases {
+        md.push_str(&format!("- **{}**", display_version(&release.version)));
+        if !release.date.is_empty() {
+            md.push_str(&format!(" ({})", release.date));
+        }
+        let mut notes = release.notes.lines().filter(|l| !l.trim().is_empty());
+        if let Some(first) = notes.next() {
+            md.push_str(&format!(": {}", first.trim()));
+        }
+        md.push('\n');
+        for line in notes {
+            md.push_str(&format!("  {}\n", line.trim()));
+        }
+    }
+    md.push('\n');
+    md
+}
+
+/// CHANGELOG.md in the Keep a Changelog format.
+pub fn to_changelog(title: &str, releases: &[Release]) -> String {
+    let mut md = String::from("# Changelog\n\n");
+    md.push_str(&format!("All notable changes to {} will be documented in this file.\n\n", title));
+    md.push_str("The format is based on [Keep a Changelog](https://keepachangelog.com/en/1.1.0/).\n");
+    for release in releases {
+        let version = release.version.trim_start_matches(['v', 'V']);
+        md.push_str(&format!("\n## [{}]", version));
+        if !release.date.is_empty() {
+            md.push_str(&format!(" - {}", release.date));
+        }
+        md.push('\n');
+        if !release.notes.is_empty() {
+            md.push('\n');
+        }
+        for line in release.notes.lines().map(str::trim).filter(|l| !l.is_empty()) {
+            // Headings (### Added) and list items pass through; plain lines
+            // become list items.
+            if line.starts_with(['#', '-', '*']) {
+                md.push_str(&format!("{}\n", line));
+            } else {
+                md.push_str(&format!("- {}\n", line));
+            }
+        }
+    }
+    md
+}
+
+/// Badge with the current (newest) version.
+pub fn render_version_badge(ui: &mut egui::Ui, lang: Language, releases: &[Release]) {
+    let Some(current) = releases.first() else {
+        return;
+    };
+    let text = egui::RichText::new(display_version(&current.version))
+        .size(12.0)
+        .color(egui::Color32::from_rgb(230, 230, 235))
+        .background_color(egui::Color32::from_rgb(60, 90, 140));
+    let hover = if current.date.is_empty() {
+        tr(lang, Msg::CurrentVersion).to_string()
+    } else {
+        fill(tr(lang, Msg::CurrentVersionSince), &[&current.date])
+    };
+    ui.label(text).on_hover_text(hover);
+}
+
+/// Editable list of releases, newest first.
+pub fn render_release_editor(ui: &mut egui::Ui, lang: Language, releases: &mut Vec<Release>) {
+    if ui.button(tr(lang, Msg::AddRelease)).clicked() {
+        releases.insert(
+            0,
+            Release {
+                version: next_version(releases.first()),
+                date: chrono::Local::now().format("%Y-%m-%d").to_string(),
+                notes: String::new(),
+            },
+        );
+    }
+
+    let mut remove = None;
+    egui::ScrollArea::vertical()
+        .id_salt("release_editor")
+        .max_height(220.0)
+        .show(ui, |ui| {
+            for (idx, release) in releases.iter_mut().enumerate() {
+                ui.add_space(4.0);
+                ui.horizontal(|ui| {
+                    ui.add(egui::TextEdit::singleline(&mut release.version).desired_width(80.0).hint_text(tr(lang, Msg::ReleaseVersion)));
+                    ui.add(egui::TextEdit::singleline(&mut release.date).desired_width(90.0).hint_text("YYYY-MM-DD"));
+                    if ui.small_button("✕").on_hover_text(tr(lang, Msg::Delete)).clicked() {
+                        remove = Some(idx);
+                    }
+                });
+                ui.add(
+                    egui::TextEdit::multiline(&mut release.notes)
+                        .desired_width(320.0)
+                        .desired_rows(2)
+                        .hint_text(tr(lang, Msg::ReleaseNotes)),
+                );
+            }
+        });
+    if let Some(idx) = remove {
+        releases.remove(idx);
+    }
+}
@@ -0,0 +1,280 @@
+//! Background writer: saves are coalesced and written off the UI thread.
+
+use crate::i18n::{fill, Msg};
+use crate::merge::FileStamp;
+use crate::storage::Storage;
+use crate::{AppData, ProductionManager};
+use eframe::egui;
+use std::path::{Path, PathBuf};
+use std::sync::mpsc::{self, Receiver, RecvTimeoutError, Sender};
+use std::sync::Arc;
+use std::thread::JoinHandle;
+use std::time::{Duration, Instant};
+
+/// Quiet time after the last change before it is written.
+const DEBOUNCE: Duration = Duration::from_millis(400);
+/// Upper bound on how long a steady stream of changes can delay a write.
+const MAX_DELAY: Duration = Duration::from_secs(2);
+
+enum Command {
+    Save(u64, Arc<AppData>),
+    /// The UI re-read the file; this is what is on disk now.
+    Sync(Arc<AppData>, Option<FileStamp>),
+    Flush(Sender<()>),
+}
+
+pub enum SaveEvent {
+    Saved {
+        generation: u64,
+        data: Arc<AppData>,
+        stamp: Option<FileStamp>,
+    },
+    Failed {
+        generation: u64,
+        error: String,
+    },
+    /// The file was changed by someone else; the write was skipped.
+    Conflict { generation: u64 },
+}
+
+#[derive(Clone, Debug, PartialEq)]
+pub enum SaveStatus {
+    Idle,
+    Saving,
+    Saved,
+    Error(String),
+}
+
+struct Writer {
+    path: PathBuf,
+    storage: Storage,
+    on_disk: Arc<AppData>,
+    stamp: Option<FileStamp>,
+    events: Sender<SaveEvent>,
+    ctx: egui::Context,
+}
+
+impl Writer {
+    fn run(mut self, commands: Receiver<Command>) {
+        let mut pending: Option<(u64, Arc<AppData>)> = None;
+        let mut first_pending = Instant::now();
+
+        loop {
+            let command = if pending.is_some() {
+                let waited = first_pending.elapsed();
+                let timeout = if waited >= MAX_DELAY { Duration::ZERO } else { DEBOUNCE.min(MAX_DELAY - waited) };
+                commands.recv_timeout(timeout)
+            } else {
+                commands.recv().map_err(|_| RecvTimeoutError::Disconnected)
+            };
+
+            match command {
+                Ok(Command::Save(generation, data)) => {
+                    if pending.is_none() {
+                        first_pending = Instant::now();
+                    }
+                    pending = Some((generation, data));
+                }
+                Ok(Command::Sync(data, stamp)) => {
+                    // Anything still pending predates the reload.
+                    pending = None;
+                    self.on_disk = data;
+                    self.stamp = stamp;
+                }
+                Ok(Command::Flush(done)) => {
+                    if let Some((generation, data)) = pending.take() {
+                        self.write(generation, data);
+                    }
+                    done.send(()).ok();
+                }
+                Err(RecvTimeoutError::Timeout) => {
+                    if let Some((generation, data)) = pending.take() {
+                        self.write(generation, data);
+                    }
+                }
+                Err(RecvTimeoutError::Disconnected) => {
+                    if let Some((generation, data)) = pending.take() {
+                        self.write(generation, data);
+                    }
+                    break;
+                }
+            }
+        }
+    }
+
+    fn write(&mut self, generation: u64, data: Arc<AppData>) {
+        // Never overwrite edits made outside the app; the UI merges first.
+        let event = if FileStamp::of(&self.path) != self.stamp {
+            SaveEvent::Conflict { generation }
+        } else {
+            match self.storage.save(&self.on_disk, &data) {
+                Ok(()) => {
+                    self.on_disk = data.clone();
+                    self.stamp = FileStamp::of(&self.path);
+                    SaveEvent::Saved {
+                        generation,
+                        data,
+                        stamp: self.stamp,
+                    }
+                }
+                Err(error) => SaveEvent::Failed { generation, error },
+            }
+        };
+        self.events.send(event).ok();
+        self.ctx.request_repaint();
+    }
+}
+
+/// Handle to the writer thread of one data file.
+pub struct Saver {
+    commands: Option<Sender<Command>>,
+    events: Receiver<SaveEvent>,
+    thread: Option<JoinHandle<()>>,
+}
+
+impl Saver {
+    pub fn new(ctx: &egui::Context, path: &Path, on_disk: Arc<AppData>, stamp: Option<FileStamp>) -> Self {
+        let (command_tx, command_rx) = mpsc::channel();
+        let (event_tx, event_rx) = mpsc::channel();
+        let writer = Writer {
+            path: path.to_path_buf(),
+            storage: Storage::open(path),
+            on_disk,
+            stamp,
+            events: event_tx,
+            ctx: ctx.clone(),
+        };
+        let thread = std::thread::Builder::new()
+            .name("saver".to_string())
+            .spawn(move || writer.run(command_rx))
+            .ok();
+        Self {
+            commands: Some(command_tx),
+            events: event_rx,
+            thread,
+        }
+    }
+
+    fn send(&self, command: Command) {
+        if let Some(commands) = &self.commands {
+            commands.send(command).ok();
+        }
+    }
+
+    pub fn save(&self, generation: u64, data: Arc<AppData>) {
+        self.send(Command::Save(generation, data));
+    }
+
+    pub fn sync(&self, data: Arc<AppData>, stamp: Option<FileStamp>) {
+        self.send(Command::Sync(data, stamp));
+    }
+
+    /// Writes anything pending and waits for it.
+    pub fn flush(&self) {
+        let (done_tx, done_rx) = mpsc::channel();
+        self.send(Command::Flush(done_tx));
+        done_rx.recv().ok();
+    }
+
+    pub fn try_event(&self) -> Option<SaveEvent> {
+        self.events.try_recv().ok()
+    }
+}
+
+impl Drop for Saver {
+    fn drop(&mut self) {
+        // Closing the channel makes the writer flush and exit.
+        self.commands = None;
+        if let Some(thread) = self.thread.take() {
+            thread.join().ok();
+        }
+    }
+}
+
+impl ProductionManager {
+    /// True while a change has been handed to the writer but not yet written.
+    pub(crate) fn save_in_flight(&self) -> bool {
+        self.save_generation != self.saved_generation
+    }
+
+    pub(crate) fn poll_saver(&mut self) {
+        while let Some(event) = self.saver.as_ref().and_then(Saver::try_event) {
+            match event {
+                SaveEvent::Saved { generation, data, stamp } => {
+                    self.saved_generation = self.saved_generation.max(generation);
+                    self.base_data = data;
+                    self.disk_stamp = stamp;
+                    if !self.save_in_flight() {
+                        self.save_status = SaveStatus::Saved;
+                    }
+                }
+                SaveEvent::Failed { generation, error } => {
+                    self.saved_generation = self.saved_generation.max(generation);
+                    self.show_status(&fill(self.t(Msg::SaveFailed), &[&error]));
+                    self.save_status = SaveStatus::Error(error);
+                }
+                SaveEvent::Conflict { generation } => {
+                    self.saved_generation = self.saved_generation.max(generation);
+                    self.save_status = SaveStatus::Idle;
+                    self.check_external_changes();
+                }
+            }
+        }
+    }
+
+    pub(crate) fn render_save_indicator(&self, ui: &mut egui::Ui) {
+        let (text, color, hover) = match &self.save_status {
+            SaveStatus::Idle => return,
+            SaveStatus::Saving => (self.t(Msg::SaveSaving), egui::Color32::from_rgb(150, 150, 155), String::new()),
+            SaveStatus::Saved => (self.t(Msg::SaveSaved), egui::Color32::from_rgb(100, 200, 100), String::new()),
+            SaveStatus::Error(error) => (self.t(Msg::SaveError), egui::Color32::from_rgb(220, 100, 100), error.clone()),
+        };
+        let response = ui.label(egui::RichText::new(text).size(14.0).color(color));
+        if !hover.is_empty() {
+            response.on_hover_text(hover);
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::ProductionManager;
+    use eframe::egui;
+    use std::fs;
+    use std::time::{Duration, SystemTime};
+
+    fn flush_and_poll(app: &mut ProductionManager) {
+        if let Some(saver) = &app.saver {
+            saver.flush();
+        }
+        app.poll_saver();
+    }
+
+    #[test]
+    fn touched_file_does_not_drop_pending_edit() {
+        let dir = std::env::temp_dir().join(format!("production-manager-{}", uuid::Uuid::new_v4()));
+        fs::create_dir_all(&dir).unwrap();
+        let path = dir.join("data.json");
+        let mut app = ProductionManager::empty(&egui::Context::default(), dir.clone());
+        app.set_data_path(path.clone());
+        app.reload_data();
+        app.save_data();
+        flush_and_poll(&mut app);
+        assert!(path.exists());
+
+        app.data.categories[0].name = "Edited".to_string();
+        app.save_data();
+        // A sync tool touches the file without changing what it holds.
+        let file = fs::File::options().write(true).open(&path).unwrap();
+        file.set_modified(SystemTime::now() + Duration::from_secs(60)).unwrap();
+        drop(file);
+        flush_and_poll(&mut app);
+        flush_and_poll(&mut app);
+
+        let on_disk = app.storage.load().unwrap().unwrap();
+        assert_eq!(on_disk.categories[0].name, "Edited");
+        assert!(!app.save_in_flight());
+        drop(app);
+        fs::remove_dir_all(&dir).ok();
+    }
+}
@@ -0,0 +1,376 @@
+//! Scans a folder for projects and proposes them as new items.
+
+use crate::i18n::{fill, Msg};
+use crate::links::ItemLinks;
+use crate::{BuiltinCategory, Item, ProductionManager};
+use eframe::egui;
+use std::collections::HashSet;
+use std::fs;
+use std::path::{Path, PathBuf};
+use std::sync::mpsc::{self, Receiver, TryRecvError};
+
+/// How deep below the chosen folder projects are looked for.
+const MAX_DEPTH: usize = 4;
+
+const SKIPPED_DIRS: &[&str] = &[
+    "node_modules", "target", "dist", "build", "out", "bin", "obj", "vendor", "packages", "__pycache__",
+];
+
+/// Dependencies that make a `package.json` a web app.
+const WEB_FRAMEWORKS: &[&str] = &[
+    "react", "react-dom", "next", "vue", "nuxt", "svelte", "@sveltejs/kit", "@angular/core", "solid-js",
+    "preact", "astro", "@remix-run/react", "gatsby", "vite", "express", "fastify", "hono",
+];
+
+/// Dependencies and settings that mark a Rust crate as a Windows app.
+const CARGO_WINDOWS_MARKERS: &[&str] = &[
+    "windows =", "windows-sys", "winapi", "native-windows-gui", "winres", "winresource", "embed-resource",
+    "cfg(windows)", "target_os = \"windows\"",
+];
+
+/// Settings that mark a .NET project as a Windows app.
+const CSPROJ_WINDOWS_MARKERS: &[&str] = &[
+    "-windows", "<UseWPF>true", "<UseWindowsForms>true", "<UseWinUI>true", "<OutputType>WinExe",
+];
+
+pub struct ScannedProject {
+    path: PathBuf,
+    title: String,
+    description: String,
+    repository: String,
+    kind: BuiltinCategory,
+    category: usize,
+    selected: bool,
+}
+
+impl ScannedProject {
+    fn new(dir: &Path, kind: BuiltinCategory, title: String, description: String, repository: String) -> Self {
+        Self {
+            path: dir.to_path_buf(),
+            title,
+            description,
+            repository,
+            kind,
+            category: 0,
+            selected: true,
+        }
+    }
+}
+
+pub struct ScanState {
+    root: PathBuf,
+    /// Set while the folder is still being walked on the scanner thread.
+    pending: Option<Receiver<Vec<ScannedProject>>>,
+    projects: Vec<ScannedProject>,
+    skipped: usize,
+}
+
+fn read_json(path: &Path) -> Option<serde_json::Value> {
+    serde_json::from_str(&fs::read_to_string(path).ok()?).ok()
+}
+
+fn json_str(value: &serde_json::Value, key: &str) -> String {
+    value.get(key).and_then(|v| v.as_str()).unwrap_or_default().to_string()
+}
+
+/// `key = "value"` from the `[package]` table of a Cargo.toml.
+fn cargo_package_value(toml: &str, key: &str) -> String {
+    let mut in_package = false;
+    for line in toml.lines().map(str::trim) {
+        if line.starts_with('[') {
+            in_package = line == "[package]";
+        } else if in_package {
+            if let Some((k, v)) = line.split_once('=') {
+                if k.trim() == key {
+                    return v.trim().trim_matches('"').to_string();
+                }
+            }
+        }
+    }
+    String::new()
+}
+
+/// Localized `__MSG_name__` placeholders in extension manifests aren't
+/// useful as titles.
+fn usable(name: String) -> Option<String> {
+    (!name.is_empty() && !name.starts_with("__MSG_")).then_some(name)
+}
+
+fn detect(dir: &Path) -> Option<ScannedProject> {
+    let folder_name = dir.file_name()?.to_string_lossy().into_owned();
+
+    if let Some(manifest) = read_json(&dir.join("manifest.json")) {
+        if manifest.get("manifest_version").is_some() {
+            let title = usable(json_str(&manifest, "name")).unwrap_or(folder_name);
+            let description = usable(json_str(&manifest, "description")).unwrap_or_default();
+            return Some(ScannedProject::new(dir, BuiltinCategory::Extensions, title, description, String::new()));
+        }
+    }
+
+    if let Some(package) = read_json(&dir.join("package.json")) {
+        let has_framework = ["dependencies", "devDependencies"].iter().any(|section| {
+            package
+                .get(section)
+                .and_then(|deps| deps.as_object())
+                .is_some_and(|deps| WEB_FRAMEWORKS.iter().any(|name| deps.contains_key(*name)))
+        });
+        if has_framework {
+            let title = usable(json_str(&package, "name")).unwrap_or(folder_name);
+            let repository = match package.get("repository") {
+                Some(serde_json::Value::String(url)) => url.clone(),
+                Some(repo) => json_str(repo, "url"),
+                None => String::new(),
+            };
+            let repository = repository.trim_start_matches("git+").to_string();
+            let description = json_str(&package, "description");
+            return Some(ScannedProject::new(dir, BuiltinCategory::WebApps, title, description, repository));
+        }
+    }
+
+    if let Ok(toml) = fs::read_to_string(dir.join("Cargo.toml")) {
+        let main = fs::read_to_string(dir.join("src/main.rs")).unwrap_or_default();
+        if CARGO_WINDOWS_MARKERS.iter().any(|m| toml.contains(m)) || main.contains("windows_subsystem") {
+            let title = usable(cargo_package_value(&toml, "name")).unwrap_or(folder_name);
+            let description = cargo_package_value(&toml, "description");
+            let repository = cargo_package_value(&toml, "repository");
+            return Some(ScannedProject::new(dir, BuiltinCategory::WindowsApps, title, description, repository));
+        }
+    }
+
+    for entry in fs::read_dir(dir).ok()?.flatten() {
+        let path = entry.path();
+        if path.extension().is_some_and(|e| e == "csproj") {
+            let content = fs::read_to_string(&path).unwrap_or_default();
+            if CSPROJ_WINDOWS_MARKERS.iter().any(|m| content.contains(m)) {
+                let title = path.file_stem().map(|s| s.to_string_lossy().into_owned()).unwrap_or(folder_name);
+                return Some(ScannedProject::new(dir, BuiltinCategory::WindowsApps, title, String::new(), String::new()));
+            }
+        }
+    }
+    None
+}
+
+fn walk(dir: &Path, depth: usize, found: &mut Vec<ScannedProject>) {
+    if let Some(project) = detect(dir) {
+        found.push(project);
+        // Don't look for projects inside projects.
+        return;
+    }
+    if depth >= MAX_DEPTH {
+        return;
+    }
+    let Ok(entries) = fs::read_dir(dir) else {
+        return;
+    };
+    let mut dirs: Vec<PathBuf> = entries
+        .flatten()
+        .filter(|e| e.file_type().is_ok_and(|t| t.is_dir()))
+        .filter(|e| {
+            let name = e.file_name().to_string_lossy().into_owned();
+            !name.starts_with('.') && !SKIPPED_DIRS.contains(&name.as_str())
+        })
+        .map(|e| e.path())
+        .collect();
+    dirs.sort();
+    for sub in dirs {
+        walk(&sub, depth + 1, found);
+    }
+}
+
+/// Normalized form of a folder path for duplicate checks.
+fn path_key(path: &Path) -> PathBuf {
+    fs::canonicalize(path).unwrap_or_else(|_| path.to_path_buf())
+}
+
+/// Item for a folder dropped onto a column, titled with the folder name.
+/// When the folder is a project, its description and repository fill the
+/// comment and link.
+pub fn item_for_folder(dir: &Path, order: usize) -> Item {
+    let title = dir.file_name().map_or_else(|| dir.display().to_string(), |n| n.to_string_lossy().into_owned());
+    let (description, repository) = detect(dir).map(|p| (p.description, p.repository)).unwrap_or_default();
+    let mut item = Item::new(title, description, order);
+    item.links = ItemLinks {
+        repository,
+        local_path: dir.display().to_string(),
+        ..ItemLinks::default()
+    };
+    item
+}
+
+impl ProductionManager {
+    /// Column for a detected project: the matching built-in category, or the
+    /// column at its default position if it was renamed away.
+    fn category_for(&self, builtin: BuiltinCategory) -> usize {
+        self.data
+            .categories
+            .iter()
+            .position(|c| c.builtin == Some(builtin))
+            .unwrap_or_else(|| {
+                let default = BuiltinCategory::ALL.iter().position(|&b| b == builtin).unwrap_or(0);
+                default.min(self.data.categories.len().saturating_sub(1))
+            })
+    }
+
+    /// Walks `root` on a worker thread; the dialog shows the projects once
+    /// it is done.
+    fn start_scan(&mut self, root: PathBuf) {
+        let (found_tx, found_rx) = mpsc::channel();
+        let ctx = self.egui_ctx.clone();
+        let dir = root.clone();
+        std::thread::Builder::new()
+            .name("scanner".to_string())
+            .spawn(move || {
+                let mut found = Vec::new();
+                walk(&dir, 0, &mut found);
+                found_tx.send(found).ok();
+                ctx.request_repaint();
+            })
+            .ok();
+
+        self.settings.scan_root = root.display().to_string();
+        self.save_settings();
+        self.scan = Some(ScanState {
+            root,
+            pending: Some(found_rx),
+            projects: Vec::new(),
+            skipped: 0,
+        });
+    }
+
+    /// Takes the scanner thread's result once it is there, leaving out
+    /// folders that are already on the board.
+    fn poll_scan(&self, state: &mut ScanState) {
+        let Some(pending) = &state.pending else {
+            return;
+        };
+        let found = match pending.try_recv() {
+            Ok(found) => found,
+            Err(TryRecvError::Empty) => return,
+            Err(TryRecvError::Disconnected) => Vec::new(),
+        };
+        state.pending = None;
+
+        let tracked: HashSet<PathBuf> = self
+            .data
+            .categories
+            .iter()
+            .flat_map(|c| c.items.iter())
+            .filter(|i| !i.links.local_path.is_empty())
+            .map(|i| path_key(Path::new(&i.links.local_path)))
+            .collect();
+        for mut project in found {
+            if tracked.contains(&path_key(&project.path)) {
+                state.skipped += 1;
+                continue;
+            }
+            project.category = self.category_for(project.kind);
+            state.projects.push(project);
+        }
+    }
+
+    pub(crate) fn choose_scan_folder(&mut self) {
+        let mut dialog = rfd::FileDialog::new();
+        if !self.settings.scan_root.is_empty() {
+            dialog = dialog.set_directory(&self.settings.scan_root);
+        }
+        if let Some(root) = dialog.pick_folder() {
+            self.start_scan(root);
+        }
+    }
+
+    fn import_scanned(&mut self, state: &ScanState) {
+        let mut added = 0;
+        for project in state.projects.iter().filter(|p| p.selected) {
+            let Some(cat) = self.data.categories.get_mut(project.category) else {
+                continue;
+            };
+            let mut item = Item::new(project.title.clone(), project.description.clone(), cat.items.len());
+            item.links = ItemLinks {
+                repository: project.repository.clone(),
+                local_path: project.path.display().to_string(),
+                ..ItemLinks::default()
+            };
+            cat.items.push(item);
+            added += 1;
+        }
+        if added > 0 {
+            self.save_data();
+        }
+        self.show_status(&fill(self.t(Msg::ScanImported), &[&added]));
+    }
+
+    pub(crate) fn render_scan_dialog(&mut self, ctx: &egui::Context) {
+        let Some(mut state) = self.scan.take() else {
+            return;
+        };
+        self.poll_scan(&mut state);
+        let mut keep_open = true;
+        let mut import = false;
+        let lang = self.settings.language;
+        let category_names: Vec<String> = self.data.categories.iter().map(|c| c.name.clone()).collect();
+
+        egui::Window::new(self.t(Msg::ScanTitle))
+            .id(egui::Id::new("scan_dialog"))
+            .collapsible(false)
+            .resizable(false)
+            .anchor(egui::Align2::CENTER_CENTER, [0.0, 0.0])
+            .show(ctx, |ui| {
+                ui.label(egui::RichText::new(state.root.display().to_string()).color(egui::Color32::from_rgb(150, 150, 155)));
+                if state.pending.is_some() {
+                    ui.horizontal(|ui| {
+                        ui.spinner();
+                        ui.label(self.t(Msg::Scanning));
+                    });
+                } else {
+                    ui.label(fill(self.t(Msg::ScanFound), &[&state.projects.len(), &state.skipped]));
+                }
+
+                ui.add_space(8.0);
+
+                egui::ScrollArea::vertical().max_height(360.0).show(ui, |ui| {
+                    ui.set_width(520.0);
+                    for (idx, project) in state.projects.iter_mut().enumerate() {
+                        ui.horizontal(|ui| {
+                            ui.checkbox(&mut project.selected, "");
+                            ui.vertical(|ui| {
+                                ui.add(egui::TextEdit::singleline(&mut project.title).desired_width(260.0));
+                                ui.label(egui::RichText::new(project.path.display().to_string()).size(12.0).color(egui::Color32::from_rgb(150, 150, 155)));
+                            });
+                            egui::ComboBox::from_id_salt(("scan_category", idx))
+                                .selected_text(category_names.get(project.category).cloned().unwrap_or_default())
+                                .show_ui(ui, |ui| {
+                                    for (cat_idx, name) in category_names.iter().enumerate() {
+                                        ui.selectable_value(&mut project.category, cat_idx, name);
+                                    }
+                                })
+                                .response
+                                .on_hover_text(project.kind.name(lang));
+                        });
+                        ui.add_space(4.0);
+                    }
+                });
+
+                ui.add_space(12.0);
+
+                ui.horizontal(|ui| {
+                    if ui.button(self.t(Msg::Cancel)).clicked() {
+                        keep_open = false;
+                    }
+                    ui.add_space(20.0);
+                    let selected = state.projects.iter().filter(|p| p.selected).count();
+                    let label = fill(self.t(Msg::ScanAddSelected), &[&selected]);
+                    if ui.add_enabled(selected > 0 && !self.read_only, egui::Button::new(label)).clicked() {
+                        import = true;
+                        keep_open = false;
+                    }
+                });
+            });
+
+        if import {
+            self.import_scanned(&state);
+        }
+        if keep_open {
+            self.scan = Some(state);
+        }
+    }
+}
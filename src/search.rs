@@ -0,0 +1,89 @@
+//! The header search box, which narrows the board and the table.
+
+use crate::fields;
+use crate::i18n::{tr, Language, Msg};
+use crate::{Category, Item, ProductionManager};
+use eframe::egui;
+use std::collections::HashSet;
+
+/// The words of a query, lowercased once for all items.
+fn query_words(query: &str) -> Vec<String> {
+    query.to_lowercase().split_whitespace().map(str::to_string).collect()
+}
+
+/// True when every word occurs in the item's title, reading, comment, tags,
+/// checklist or custom field values. Case-insensitive.
+fn matches(cat: &Category, item: &Item, words: &[String], lang: Language) -> bool {
+    let mut haystack = format!("{}\n{}\n{}", item.title, item.reading, item.comment);
+    for tag in &item.tags {
+        haystack.push_str(&format!("\n#{}", tag));
+    }
+    for entry in &item.checklist {
+        haystack.push_str(&format!("\n{}", entry.text));
+    }
+    for (def, value) in fields::filled(&cat.fields, &item.fields) {
+        haystack.push_str(&format!("\n{}: {}", def.name, def.display(lang, value)));
+    }
+    let haystack = haystack.to_lowercase();
+    words.iter().all(|word| haystack.contains(word.as_str()))
+}
+
+/// Ids of the items matching a query, kept until the query, the language or
+/// the board changes.
+pub struct SearchHits {
+    query: String,
+    lang: Language,
+    revision: u64,
+    ids: HashSet<String>,
+}
+
+impl ProductionManager {
+    /// Brings the hits up to date with the query and the board. Called once
+    /// per frame before anything is filtered.
+    pub(crate) fn refresh_search(&mut self) {
+        let lang = self.settings.language;
+        if self.search.trim().is_empty() {
+            self.search_hits = None;
+            return;
+        }
+        let current = self
+            .search_hits
+            .as_ref()
+            .is_some_and(|h| h.query == self.search && h.lang == lang && h.revision == self.data_revision);
+        if current {
+            return;
+        }
+        let words = query_words(&self.search);
+        let words = &words;
+        let ids = self
+            .data
+            .categories
+            .iter()
+            .flat_map(|cat| cat.items.iter().filter(move |item| matches(cat, item, words, lang)))
+            .map(|item| item.id.clone())
+            .collect();
+        self.search_hits = Some(SearchHits {
+            query: self.search.clone(),
+            lang,
+            revision: self.data_revision,
+            ids,
+        });
+    }
+
+    /// False for items filtered out by the search.
+    pub(crate) fn is_search_hit(&self, item: &Item) -> bool {
+        self.search_hits.as_ref().is_none_or(|h| h.ids.contains(&item.id))
+    }
+}
+
+/// Search box with a clear button. Returns true when the query changed.
+pub fn render_search_box(ui: &mut egui::Ui, lang: Language, query: &mut String) -> bool {
+    let mut changed = ui
+        .add(egui::TextEdit::singleline(query).desired_width(180.0).hint_text(tr(lang, Msg::SearchHint)))
+        .changed();
+    if !query.is_empty() && ui.small_button("✖").on_hover_text(tr(lang, Msg::SearchClear)).clicked() {
+        query.clear();
+        changed = true;
+    }
+    changed
+}
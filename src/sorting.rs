@@ -0,0 +1,102 @@
+//! Per-category display order. Sorting never touches `Item::order`, so the
+//! manual order is always there to switch back to.
+
+use crate::collate;
+use crate::i18n::{tr, Language, Msg};
+use crate::{Category, Item};
+use eframe::egui;
+use serde::{Deserialize, Serialize};
+use std::cmp::{Ordering, Reverse};
+
+#[derive(Serialize, Deserialize, Clone, Copy, Debug, PartialEq, Eq, Default)]
+pub enum SortMode {
+    #[default]
+    Manual,
+    TitleAsc,
+    TitleDesc,
+    /// Newest first.
+    Created,
+    /// Most recently changed first.
+    Updated,
+    /// Earliest due date first, items without one last.
+    Due,
+}
+
+impl SortMode {
+    const ALL: [SortMode; 6] = [
+        SortMode::Manual,
+        SortMode::TitleAsc,
+        SortMode::TitleDesc,
+        SortMode::Created,
+        SortMode::Updated,
+        SortMode::Due,
+    ];
+
+    pub fn is_manual(&self) -> bool {
+        *self == SortMode::Manual
+    }
+
+    pub fn name(self, lang: Language) -> &'static str {
+        let msg = match self {
+            SortMode::Manual => Msg::SortManual,
+            SortMode::TitleAsc => Msg::SortTitleAsc,
+            SortMode::TitleDesc => Msg::SortTitleDesc,
+            SortMode::Created => Msg::SortCreated,
+            SortMode::Updated => Msg::SortUpdated,
+            SortMode::Due => Msg::SortDue,
+        };
+        tr(lang, msg)
+    }
+
+    fn compare(self, a: &Item, b: &Item) -> Ordering {
+        match self {
+            SortMode::Manual => a.order.cmp(&b.order),
+            SortMode::TitleAsc => collate::compare(a.sort_name(), b.sort_name()),
+            SortMode::TitleDesc => collate::compare(b.sort_name(), a.sort_name()),
+            SortMode::Created => b.created_at.cmp(&a.created_at),
+            SortMode::Updated => b.updated().cmp(a.updated()),
+            SortMode::Due => match (a.due, b.due) {
+                (Some(a), Some(b)) => a.cmp(&b),
+                (Some(_), None) => Ordering::Less,
+                (None, Some(_)) => Ordering::Greater,
+                (None, None) => Ordering::Equal,
+            },
+        }
+        // Ties keep the manual order.
+        .then(a.order.cmp(&b.order))
+    }
+}
+
+impl Category {
+    /// Item indices in display order.
+    pub fn sorted_indices(&self) -> Vec<usize> {
+        let mut indices: Vec<usize> = (0..self.items.len()).collect();
+        let items = &self.items;
+        match self.sort {
+            // Collation keys are built once per item, not per comparison.
+            SortMode::TitleAsc => indices.sort_by_cached_key(|&i| (collate::key(items[i].sort_name()), items[i].order)),
+            SortMode::TitleDesc => indices.sort_by_cached_key(|&i| (Reverse(collate::key(items[i].sort_name())), items[i].order)),
+            mode => indices.sort_by(|&a, &b| mode.compare(&items[a], &items[b])),
+        }
+        indices
+    }
+}
+
+/// Sort selector for a column header. Returns true when the mode changed.
+pub fn render_sort_selector(ui: &mut egui::Ui, lang: Language, id: usize, sort: &mut SortMode) -> bool {
+    let before = *sort;
+    let mut text = egui::RichText::new(format!("⇅ {}", sort.name(lang)));
+    if !sort.is_manual() {
+        text = text.color(egui::Color32::from_rgb(230, 190, 90));
+    }
+    egui::ComboBox::from_id_salt(("category_sort", id))
+        .selected_text(text)
+        .show_ui(ui, |ui| {
+            for mode in SortMode::ALL {
+                ui.selectable_value(sort, mode, mode.name(lang));
+            }
+        })
+        .response
+        .on_hover_text(tr(lang, Msg::SortHint));
+    *sort != before
+}
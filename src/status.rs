@@ -0,0 +1,126 @@
+//! Workflow status, due date and planned schedule of items.
+
+use crate::i18n::{tr, Language, Msg};
+use crate::Item;
+use chrono::{Local, NaiveDate};
+use eframe::egui;
+use serde::{Deserialize, Serialize};
+
+#[derive(Serialize, Deserialize, Clone, Copy, Debug, PartialEq, Eq, Hash, Default)]
+pub enum ItemStatus {
+    #[default]
+    Todo,
+    InProgress,
+    Done,
+}
+
+impl ItemStatus {
+    pub const ALL: [ItemStatus; 3] = [ItemStatus::Todo, ItemStatus::InProgress, ItemStatus::Done];
+
+    pub fn is_todo(&self) -> bool {
+        *self == ItemStatus::Todo
+    }
+
+    pub fn name(self, lang: Language) -> &'static str {
+        let msg = match self {
+            ItemStatus::Todo => Msg::StatusTodo,
+            ItemStatus::InProgress => Msg::StatusInProgress,
+            ItemStatus::Done => Msg::StatusDone,
+        };
+        tr(lang, msg)
+    }
+
+    pub fn color(self) -> egui::Color32 {
+        match self {
+            ItemStatus::Todo => egui::Color32::from_rgb(120, 120, 130),
+            ItemStatus::InProgress => egui::Color32::from_rgb(70, 130, 200),
+            ItemStatus::Done => egui::Color32::from_rgb(80, 170, 100),
+        }
+    }
+}
+
+pub fn today() -> NaiveDate {
+    Local::now().date_naive()
+}
+
+pub fn parse_date(text: &str) -> Option<NaiveDate> {
+    NaiveDate::parse_from_str(text.trim(), "%Y-%m-%d").ok()
+}
+
+impl Item {
+    /// The only place status changes; keeps `completed_at` in step.
+    pub fn set_status(&mut self, status: ItemStatus) {
+        if status == self.status {
+            return;
+        }
+        self.status = status;
+        self.completed_at = (status == ItemStatus::Done).then(|| Local::now().format("%Y-%m-%d %H:%M").to_string());
+    }
+
+    pub fn is_overdue(&self, today: NaiveDate) -> bool {
+        self.status != ItemStatus::Done && self.due.is_some_and(|due| due < today)
+    }
+}
+
+/// Status and due date line on a card. Nothing is drawn for an open item
+/// without a due date.
+pub fn render_status_line(ui: &mut egui::Ui, lang: Language, item: &Item) {
+    if item.status.is_todo() && item.due.is_none() {
+        return;
+    }
+    ui.horizontal_wrapped(|ui| {
+        ui.spacing_mut().item_spacing.x = 6.0;
+        if !item.status.is_todo() {
+            let text = egui::RichText::new(item.status.name(lang))
+                .size(12.0)
+                .color(egui::Color32::WHITE)
+                .background_color(item.status.color());
+            ui.label(text);
+        }
+        if let Some(due) = item.due {
+            let color = if item.is_overdue(today()) {
+                egui::Color32::from_rgb(220, 100, 100)
+            } else {
+                egui::Color32::from_rgb(150, 150, 155)
+            };
+            ui.label(egui::RichText::new(format!("📅 {}", due.format("%Y-%m-%d"))).size(13.0).color(color))
+                .on_hover_text(tr(lang, Msg::DueDate));
+        }
+    });
+}
+
+fn date_field(ui: &mut egui::Ui, text: &mut String) {
+    let valid = text.trim().is_empty() || parse_date(text).is_some();
+    let mut edit = egui::TextEdit::singleline(text).desired_width(90.0).hint_text("YYYY-MM-DD");
+    if !valid {
+        edit = edit.text_color(egui::Color32::from_rgb(220, 100, 100));
+    }
+    ui.add(edit);
+}
+
+/// Planned start and end date fields for the edit popup.
+pub fn render_schedule_editor(ui: &mut egui::Ui, lang: Language, start: &mut String, end: &mut String) {
+    ui.horizontal(|ui| {
+        ui.label(tr(lang, Msg::ScheduleLabel));
+        date_field(ui, start);
+        ui.label("–");
+        date_field(ui, end);
+    });
+}
+
+/// Status selector and due date field for the edit popup.
+pub fn render_status_editor(ui: &mut egui::Ui, lang: Language, status: &mut ItemStatus, due: &mut String) {
+    ui.horizontal(|ui| {
+        ui.label(tr(lang, Msg::StatusLabel));
+        egui::ComboBox::from_id_salt("status_select")
+            .selected_text(status.name(lang))
+            .show_ui(ui, |ui| {
+                for option in ItemStatus::ALL {
+                    ui.selectable_value(status, option, option.name(lang));
+                }
+            });
+        ui.add_space(12.0);
+        ui.label(tr(lang, Msg::DueDateLabel));
+        date_field(ui, due);
+    });
+}
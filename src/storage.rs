@@ -0,0 +1,284 @@
+//! Persistence backends. JSON is the default; a data file ending in `.db`
+//! or `.sqlite` is stored in SQLite and written incrementally.
+
+use crate::i18n::{fill, Msg};
+use crate::{AppData, Category, Item, ProductionManager};
+use eframe::egui;
+use rusqlite::{params, Connection, OpenFlags, OptionalExtension};
+use std::collections::HashMap;
+use std::fs;
+use std::path::{Path, PathBuf};
+
+const SCHEMA_VERSION: i64 = 1;
+
+const SCHEMA: &str = "
+    CREATE TABLE IF NOT EXISTS meta (
+        key   TEXT PRIMARY KEY,
+        value TEXT NOT NULL
+    );
+    CREATE TABLE IF NOT EXISTS categories (
+        position INTEGER PRIMARY KEY,
+        name     TEXT NOT NULL,
+        data     TEXT NOT NULL
+    );
+    CREATE TABLE IF NOT EXISTS items (
+        id         TEXT PRIMARY KEY,
+        category   INTEGER NOT NULL,
+        position   INTEGER NOT NULL,
+        title      TEXT NOT NULL,
+        comment    TEXT NOT NULL,
+        created_at TEXT NOT NULL,
+        data       TEXT NOT NULL
+    );
+    CREATE INDEX IF NOT EXISTS items_by_category ON items (category, position);
+";
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum StorageKind {
+    Json,
+    Sqlite,
+}
+
+impl StorageKind {
+    pub fn for_path(path: &Path) -> Self {
+        match path.extension().and_then(|e| e.to_str()).map(str::to_ascii_lowercase).as_deref() {
+            Some("db" | "sqlite" | "sqlite3") => StorageKind::Sqlite,
+            _ => StorageKind::Json,
+        }
+    }
+
+    pub fn label(self) -> &'static str {
+        match self {
+            StorageKind::Json => "JSON",
+            StorageKind::Sqlite => "SQLite",
+        }
+    }
+}
+
+pub enum Storage {
+    Json(PathBuf),
+    Sqlite { path: PathBuf, conn: Option<Connection> },
+}
+
+impl Storage {
+    pub fn open(path: &Path) -> Self {
+        match StorageKind::for_path(path) {
+            StorageKind::Json => Storage::Json(path.to_path_buf()),
+            StorageKind::Sqlite => Storage::Sqlite {
+                path: path.to_path_buf(),
+                conn: None,
+            },
+        }
+    }
+
+    pub fn kind(&self) -> StorageKind {
+        match self {
+            Storage::Json(_) => StorageKind::Json,
+            Storage::Sqlite { .. } => StorageKind::Sqlite,
+        }
+    }
+
+    /// Reads the board. `Ok(None)` means there is no data file yet.
+    pub fn load(&self) -> Result<Option<AppData>, String> {
+        match self {
+            Storage::Json(path) => {
+                if !path.exists() {
+                    return Ok(None);
+                }
+                let content = fs::read_to_string(path).map_err(|e| e.to_string())?;
+                serde_json::from_str(&content).map(Some).map_err(|e| e.to_string())
+            }
+            Storage::Sqlite { path, .. } => {
+                if !path.exists() {
+                    return Ok(None);
+                }
+                load_sqlite(path).map_err(|e| e.to_string())
+            }
+        }
+    }
+
+    /// Writes `data`. `previous` is what the file currently holds; the SQLite
+    /// backend only touches the rows that differ from it.
+    pub fn save(&mut self, previous: &AppData, data: &AppData) -> Result<(), String> {
+        match self {
+            Storage::Json(path) => {
+                let json = serde_json::to_string_pretty(data).map_err(|e| e.to_string())?;
+                fs::write(path, json).map_err(|e| e.to_string())
+            }
+            Storage::Sqlite { path, conn } => {
+                if conn.is_none() {
+                    *conn = Some(open_sqlite(path).map_err(|e| e.to_string())?);
+                }
+                let conn = conn.as_mut().unwrap();
+                save_sqlite(conn, previous, data).map_err(|e| e.to_string())
+            }
+        }
+    }
+}
+
+fn open_sqlite(path: &Path) -> rusqlite::Result<Connection> {
+    let conn = Connection::open(path)?;
+    conn.execute_batch(SCHEMA)?;
+    conn.execute(
+        "INSERT OR IGNORE INTO meta (key, value) VALUES ('schema_version', ?1)",
+        params![SCHEMA_VERSION.to_string()],
+    )?;
+    Ok(conn)
+}
+
+fn to_json<T: serde::Serialize>(value: &T) -> rusqlite::Result<String> {
+    serde_json::to_string(value).map_err(|e| rusqlite::Error::ToSqlConversionFailure(Box::new(e)))
+}
+
+fn from_json<T: serde::de::DeserializeOwned>(json: &str) -> rusqlite::Result<T> {
+    serde_json::from_str(json).map_err(|e| rusqlite::Error::FromSqlConversionFailure(0, rusqlite::types::Type::Text, Box::new(e)))
+}
+
+fn load_sqlite(path: &Path) -> rusqlite::Result<Option<AppData>> {
+    let conn = Connection::open_with_flags(path, OpenFlags::SQLITE_OPEN_READ_ONLY)?;
+    let app: Option<String> = conn
+        .query_row("SELECT value FROM meta WHERE key = 'app'", [], |row| row.get(0))
+        .optional()?;
+    let Some(app) = app else {
+        return Ok(None);
+    };
+    let mut data: AppData = from_json(&app)?;
+
+    let mut stmt = conn.prepare("SELECT data FROM categories ORDER BY position")?;
+    let categories = stmt.query_map([], |row| row.get::<_, String>(0))?;
+    for json in categories {
+        data.categories.push(from_json(&json?)?);
+    }
+
+    let mut stmt = conn.prepare("SELECT category, data FROM items ORDER BY category, position")?;
+    let items = stmt.query_map([], |row| Ok((row.get::<_, i64>(0)?, row.get::<_, String>(1)?)))?;
+    for row in items {
+        let (cat_idx, json) = row?;
+        let item: Item = from_json(&json)?;
+        if let Some(cat) = data.categories.get_mut(cat_idx as usize) {
+            cat.items.push(item);
+        }
+    }
+    Ok(Some(data))
+}
+
+fn save_sqlite(conn: &mut Connection, previous: &AppData, data: &AppData) -> rusqlite::Result<()> {
+    let tx = conn.transaction()?;
+    let has_app: bool = tx
+        .query_row("SELECT 1 FROM meta WHERE key = 'app'", [], |_| Ok(()))
+        .optional()?
+        .is_some();
+    // A new database gets everything; otherwise only what changed.
+    let previous = if has_app {
+        Some(previous)
+    } else {
+        tx.execute("DELETE FROM categories", [])?;
+        tx.execute("DELETE FROM items", [])?;
+        None
+    };
+
+    let app = data.header();
+    if previous.map(AppData::header).as_ref() != Some(&app) {
+        tx.execute(
+            "INSERT OR REPLACE INTO meta (key, value) VALUES ('app', ?1)",
+            params![to_json(&app)?],
+        )?;
+    }
+
+    for (position, cat) in data.categories.iter().enumerate() {
+        let header = cat.header();
+        let old = previous.and_then(|p| p.categories.get(position)).map(Category::header);
+        if old.as_ref() != Some(&header) {
+            tx.execute(
+                "INSERT OR REPLACE INTO categories (position, name, data) VALUES (?1, ?2, ?3)",
+                params![position as i64, header.name, to_json(&header)?],
+            )?;
+        }
+    }
+    tx.execute("DELETE FROM categories WHERE position >= ?1", params![data.categories.len() as i64])?;
+
+    let mut old_items: HashMap<&str, (usize, usize, &Item)> = HashMap::new();
+    if let Some(previous) = previous {
+        for (cat_idx, cat) in previous.categories.iter().enumerate() {
+            for (pos, item) in cat.items.iter().enumerate() {
+                old_items.insert(item.id.as_str(), (cat_idx, pos, item));
+            }
+        }
+    }
+    {
+        let mut upsert = tx.prepare(
+            "INSERT OR REPLACE INTO items (id, category, position, title, comment, created_at, data)
+             VALUES (?1, ?2, ?3, ?4, ?5, ?6, ?7)",
+        )?;
+        for (cat_idx, cat) in data.categories.iter().enumerate() {
+            for (pos, item) in cat.items.iter().enumerate() {
+                if old_items.remove(item.id.as_str()) == Some((cat_idx, pos, item)) {
+                    continue;
+                }
+                upsert.execute(params![
+                    item.id,
+                    cat_idx as i64,
+                    pos as i64,
+                    item.title,
+                    item.comment,
+                    item.created_at,
+                    to_json(item)?,
+                ])?;
+            }
+        }
+        let mut delete = tx.prepare("DELETE FROM items WHERE id = ?1")?;
+        for id in old_items.keys() {
+            delete.execute(params![id])?;
+        }
+    }
+
+    tx.commit()
+}
+
+impl ProductionManager {
+    /// Copies the current JSON board into an SQLite database next to it and
+    /// switches to it. The JSON file is left in place as a backup.
+    fn migrate_to_sqlite(&mut self) {
+        let target = self.data_path.with_extension("db");
+        if target.exists() {
+            self.show_status(&fill(self.t(Msg::MigrationTargetExists), &[&target.display()]));
+            return;
+        }
+        let mut store = Storage::open(&target);
+        if let Err(err) = store.save(&self.data, &self.data) {
+            fs::remove_file(&target).ok();
+            self.show_status(&fill(self.t(Msg::SaveFailed), &[&err]));
+            return;
+        }
+        drop(store);
+
+        if !self.data_override {
+            let current = self.settings.current_workspace.clone();
+            if let Some(workspace) = self.settings.workspaces.iter_mut().find(|w| w.name == current) {
+                workspace.file = Path::new(&workspace.file).with_extension("db").to_string_lossy().into_owned();
+            }
+            self.save_settings();
+        }
+        self.set_data_path(target);
+        self.acquire_lock();
+        self.reload_data();
+        self.show_status(self.t(Msg::MigratedToSqlite));
+    }
+
+    pub(crate) fn render_storage_settings(&mut self, ui: &mut egui::Ui) {
+        ui.horizontal(|ui| {
+            ui.label(self.t(Msg::StorageLabel));
+            ui.strong(self.storage.kind().label());
+        });
+        if self.storage.kind() == StorageKind::Json {
+            let button = egui::Button::new(self.t(Msg::MigrateToSqlite));
+            if ui
+                .add_enabled(!self.read_only, button)
+                .on_hover_text(self.t(Msg::MigrateToSqliteHint))
+                .clicked()
+            {
+                self.migrate_to_sqlite();
+            }
+        }
+    }
+}
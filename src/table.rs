@@ -0,0 +1,306 @@
+//! Table view: one row per item across all categories, with sortable
+//! columns and inline editing of title and status.
+
+use crate::collate;
+use crate::i18n::{tr, Msg};
+use crate::status::ItemStatus;
+use crate::{tags, CardAction, Item, ProductionManager};
+use eframe::egui;
+use egui_extras::{Column, TableBuilder};
+use serde::{Deserialize, Serialize};
+use std::cmp::{Ordering, Reverse};
+
+const ROW_HEIGHT: f32 = 26.0;
+
+#[derive(Serialize, Deserialize, Clone, Copy, Debug, PartialEq, Eq)]
+pub enum TableColumn {
+    Category,
+    Title,
+    Status,
+    Tags,
+    Created,
+    Due,
+    Schedule,
+    Comment,
+}
+
+impl TableColumn {
+    const ALL: [TableColumn; 8] = [
+        TableColumn::Category,
+        TableColumn::Title,
+        TableColumn::Status,
+        TableColumn::Tags,
+        TableColumn::Created,
+        TableColumn::Due,
+        TableColumn::Schedule,
+        TableColumn::Comment,
+    ];
+
+    fn msg(self) -> Msg {
+        match self {
+            TableColumn::Category => Msg::CsvCategory,
+            TableColumn::Title => Msg::ColumnTitle,
+            TableColumn::Status => Msg::ColumnStatus,
+            TableColumn::Tags => Msg::ColumnTags,
+            TableColumn::Created => Msg::MarkdownCreated,
+            TableColumn::Due => Msg::DueDate,
+            TableColumn::Schedule => Msg::ColumnSchedule,
+            TableColumn::Comment => Msg::ColumnComment,
+        }
+    }
+
+    fn width(self) -> Column {
+        match self {
+            TableColumn::Title => Column::initial(220.0).at_least(120.0),
+            TableColumn::Comment => Column::remainder().at_least(120.0),
+            TableColumn::Schedule => Column::initial(170.0),
+            TableColumn::Tags => Column::initial(150.0),
+            _ => Column::auto().at_least(70.0),
+        }
+        .resizable(true)
+        .clip(true)
+    }
+}
+
+/// Title being edited in place: item id and the text typed so far.
+pub struct TitleEdit {
+    item_id: String,
+    text: String,
+    focused: bool,
+}
+
+enum TableAction {
+    Rename(usize, usize, String),
+    SetStatus(usize, usize, ItemStatus),
+    Open(usize, usize),
+}
+
+/// Missing values sort last in both directions.
+fn cmp_option<T: Ord>(a: Option<T>, b: Option<T>) -> Ordering {
+    match (a, b) {
+        (Some(a), Some(b)) => a.cmp(&b),
+        (Some(_), None) => Ordering::Less,
+        (None, Some(_)) => Ordering::Greater,
+        (None, None) => Ordering::Equal,
+    }
+}
+
+fn compare(column: TableColumn, (ca, a): (usize, &Item), (cb, b): (usize, &Item)) -> Ordering {
+    match column {
+        TableColumn::Category => ca.cmp(&cb).then(a.order.cmp(&b.order)),
+        TableColumn::Title => collate::compare(a.sort_name(), b.sort_name()),
+        TableColumn::Status => {
+            let rank = |s: ItemStatus| ItemStatus::ALL.iter().position(|&x| x == s);
+            rank(a.status).cmp(&rank(b.status))
+        }
+        TableColumn::Tags => match (a.tags.first(), b.tags.first()) {
+            (Some(x), Some(y)) => collate::compare(x, y),
+            (x, y) => cmp_option(x, y),
+        },
+        TableColumn::Created => a.created_at.cmp(&b.created_at),
+        TableColumn::Due => cmp_option(a.due, b.due),
+        TableColumn::Schedule => cmp_option(a.start_date, b.start_date),
+        TableColumn::Comment => a.comment.to_lowercase().cmp(&b.comment.to_lowercase()),
+    }
+}
+
+fn comment_preview(comment: &str) -> &str {
+    comment.lines().next().unwrap_or_default()
+}
+
+impl ProductionManager {
+    fn render_table_toolbar(&mut self, ui: &mut egui::Ui) {
+        ui.horizontal(|ui| {
+            ui.menu_button(self.t(Msg::TableColumns), |ui| {
+                for column in TableColumn::ALL {
+                    let mut shown = !self.settings.hidden_columns.contains(&column);
+                    let enabled = column != TableColumn::Title;
+                    if ui.add_enabled(enabled, egui::Checkbox::new(&mut shown, self.t(column.msg()))).changed() {
+                        if shown {
+                            self.settings.hidden_columns.retain(|c| *c != column);
+                        } else {
+                            self.settings.hidden_columns.push(column);
+                        }
+                        self.save_settings();
+                    }
+                }
+            });
+            ui.label(egui::RichText::new(self.t(Msg::TableHint)).size(13.0).color(egui::Color32::from_rgb(150, 150, 155)));
+        });
+    }
+
+    pub(crate) fn render_table(&mut self, ui: &mut egui::Ui) {
+        self.render_table_toolbar(ui);
+        ui.add_space(6.0);
+
+        let lang = self.settings.language;
+        let columns: Vec<TableColumn> = TableColumn::ALL
+            .into_iter()
+            .filter(|c| *c == TableColumn::Title || !self.settings.hidden_columns.contains(c))
+            .collect();
+
+        let data = &self.data;
+        let mut rows: Vec<(usize, usize)> = data
+            .categories
+            .iter()
+            .enumerate()
+            .flat_map(|(cat_idx, cat)| (0..cat.items.len()).map(move |item_idx| (cat_idx, item_idx)))
+            .filter(|&(c, i)| self.is_search_hit(&data.categories[c].items[i]))
+            .collect();
+        match self.table_sort {
+            // Collation keys are built once per row, not per comparison.
+            Some((TableColumn::Title, descending)) => {
+                let title = |&(c, i): &(usize, usize)| collate::key(data.categories[c].items[i].sort_name());
+                if descending {
+                    rows.sort_by_cached_key(|row| Reverse(title(row)));
+                } else {
+                    rows.sort_by_cached_key(title);
+                }
+            }
+            Some((column, descending)) => {
+                let item = |&(c, i): &(usize, usize)| (c, &data.categories[c].items[i]);
+                rows.sort_by(|a, b| {
+                    let ordering = compare(column, item(a), item(b));
+                    if descending { ordering.reverse() } else { ordering }
+                });
+            }
+            None => {}
+        }
+
+        let sort = &mut self.table_sort;
+        let title_edit = &mut self.title_edit;
+        let mut action = None;
+
+        let mut table = TableBuilder::new(ui)
+            .id_salt("item_table")
+            .striped(true)
+            .resizable(true)
+            .auto_shrink([false, false])
+            .cell_layout(egui::Layout::left_to_right(egui::Align::Center));
+        if self.search_changed {
+            table = table.vertical_scroll_offset(0.0);
+        }
+        for column in &columns {
+            table = table.column(column.width());
+        }
+
+        table
+            .header(ROW_HEIGHT, |mut header| {
+                for &column in &columns {
+                    header.col(|ui| {
+                        let arrow = match *sort {
+                            Some((c, false)) if c == column => " ▲",
+                            Some((c, true)) if c == column => " ▼",
+                            _ => "",
+                        };
+                        let label = egui::RichText::new(format!("{}{}", tr(lang, column.msg()), arrow)).strong();
+                        if ui.add(egui::Button::new(label).frame(false)).clicked() {
+                            // Ascending, then descending, then back to board order.
+                            *sort = match *sort {
+                                Some((c, false)) if c == column => Some((column, true)),
+                                Some((c, true)) if c == column => None,
+                                _ => Some((column, false)),
+                            };
+                        }
+                    });
+                }
+            })
+            .body(|body| {
+                body.rows(ROW_HEIGHT, rows.len(), |mut row| {
+                    let (cat_idx, item_idx) = rows[row.index()];
+                    let cat = &data.categories[cat_idx];
+                    let item = &cat.items[item_idx];
+                    for &column in &columns {
+                        row.col(|ui| match column {
+                            TableColumn::Category => {
+                                ui.label(egui::RichText::new("■").color(cat.color(cat_idx)));
+                                ui.label(&cat.name);
+                            }
+                            TableColumn::Title => {
+                                if ui.small_button("✎").on_hover_text(tr(lang, Msg::EditItem)).clicked() {
+                                    action = Some(TableAction::Open(cat_idx, item_idx));
+                                }
+                                match title_edit.as_mut().filter(|e| e.item_id == item.id) {
+                                    Some(edit) => {
+                                        let response = ui.add(egui::TextEdit::singleline(&mut edit.text).desired_width(f32::INFINITY));
+                                        if !edit.focused {
+                                            response.request_focus();
+                                            edit.focused = true;
+                                        }
+                                        if response.lost_focus() {
+                                            let cancelled = ui.input(|i| i.key_pressed(egui::Key::Escape));
+                                            let text = edit.text.trim().to_string();
+                                            if !cancelled && !text.is_empty() && text != item.title {
+                                                action = Some(TableAction::Rename(cat_idx, item_idx, text));
+                                            }
+                                            *title_edit = None;
+                                        }
+                                    }
+                                    None => {
+                                        let response = ui
+                                            .add(egui::Label::new(&item.title).truncate().sense(egui::Sense::click()))
+                                            .on_hover_text(tr(lang, Msg::TableRenameHint));
+                                        if response.clicked() {
+                                            *title_edit = Some(TitleEdit {
+                                                item_id: item.id.clone(),
+                                                text: item.title.clone(),
+                                                focused: false,
+                                            });
+                                        }
+                                    }
+                                }
+                            }
+                            TableColumn::Status => {
+                                let mut status = item.status;
+                                egui::ComboBox::from_id_salt(("table_status", &item.id))
+                                    .selected_text(egui::RichText::new(status.name(lang)).color(status.color()))
+                                    .show_ui(ui, |ui| {
+                                        for option in ItemStatus::ALL {
+                                            ui.selectable_value(&mut status, option, option.name(lang));
+                                        }
+                                    });
+                                if status != item.status {
+                                    action = Some(TableAction::SetStatus(cat_idx, item_idx, status));
+                                }
+                            }
+                            TableColumn::Tags => tags::render_tags(ui, &item.tags),
+                            TableColumn::Created => {
+                                ui.label(&item.created_at);
+                            }
+                            TableColumn::Due => {
+                                if let Some(due) = item.due {
+                                    let color = if item.is_overdue(crate::status::today()) {
+                                        egui::Color32::from_rgb(220, 100, 100)
+                                    } else {
+                                        ui.visuals().text_color()
+                                    };
+                                    ui.label(egui::RichText::new(due.format("%Y-%m-%d").to_string()).color(color));
+                                }
+                            }
+                            TableColumn::Schedule => {
+                                if let (Some(start), Some(end)) = (item.start_date, item.end_date) {
+                                    ui.label(format!("{} – {}", start.format("%Y-%m-%d"), end.format("%m-%d")));
+                                }
+                            }
+                            TableColumn::Comment => {
+                                ui.add(egui::Label::new(egui::RichText::new(comment_preview(&item.comment)).color(egui::Color32::from_rgb(180, 180, 185))).truncate())
+                                    .on_hover_text(&item.comment);
+                            }
+                        });
+                    }
+                });
+            });
+
+        match action {
+            Some(TableAction::Rename(cat_idx, item_idx, title)) => {
+                let item = &mut self.data.categories[cat_idx].items[item_idx];
+                item.title = title;
+                item.touch();
+                self.save_data();
+            }
+            Some(TableAction::SetStatus(cat_idx, item_idx, status)) => self.request_status(cat_idx, item_idx, status),
+            Some(TableAction::Open(cat_idx, item_idx)) => self.apply_card_action(cat_idx, item_idx, CardAction::Edit),
+            None => {}
+        }
+    }
+}
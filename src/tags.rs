@@ -0,0 +1,31 @@
+//! Free-form tags on items.
+
+use eframe::egui;
+
+/// Splits a comma separated list (ASCII or Japanese commas), dropping
+/// blanks and duplicates but keeping the order.
+pub fn parse_tags(text: &str) -> Vec<String> {
+    let mut tags: Vec<String> = Vec::new();
+    for tag in text.split([',', '、', '，']).map(|t| t.trim().trim_start_matches('#').trim()) {
+        if !tag.is_empty() && !tags.iter().any(|t| t == tag) {
+            tags.push(tag.to_string());
+        }
+    }
+    tags
+}
+
+pub fn join_tags(tags: &[String]) -> String {
+    tags.join(", ")
+}
+
+/// Small `#tag` labels.
+pub fn render_tags(ui: &mut egui::Ui, tags: &[String]) {
+    for tag in tags {
+        ui.label(
+            egui::RichText::new(format!("#{}", tag))
+                .size(12.0)
+                .color(egui::Color32::from_rgb(170, 190, 230))
+                .background_color(egui::Color32::from_rgb(50, 60, 80)),
+        );
+    }
+}
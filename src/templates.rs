@@ -0,0 +1,319 @@
+//! Reusable item templates stored with the board, and item checklists.
+
+use crate::i18n::{tr, Language, Msg};
+use crate::status::{today, ItemStatus};
+use crate::{tags, Item, ProductionManager};
+use eframe::egui;
+use serde::{Deserialize, Serialize};
+
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq)]
+pub struct ChecklistEntry {
+    pub text: String,
+    #[serde(default)]
+    pub done: bool,
+}
+
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq)]
+pub struct Template {
+    pub id: String,
+    pub name: String,
+    /// Title prefill; `{date}`, `{category}` and `{n}` (the next item number
+    /// in the category) are replaced.
+    #[serde(default)]
+    pub title: String,
+    #[serde(default)]
+    pub comment: String,
+    #[serde(default)]
+    pub tags: Vec<String>,
+    #[serde(default)]
+    pub checklist: Vec<String>,
+    #[serde(default)]
+    pub status: ItemStatus,
+}
+
+impl Template {
+    fn new(name: String) -> Self {
+        Self {
+            id: uuid::Uuid::new_v4().to_string(),
+            name,
+            title: String::new(),
+            comment: String::new(),
+            tags: Vec::new(),
+            checklist: Vec::new(),
+            status: ItemStatus::default(),
+        }
+    }
+
+    pub fn expand_title(&self, category: &str, next: usize) -> String {
+        self.title
+            .replace("{date}", &today().format("%Y-%m-%d").to_string())
+            .replace("{category}", category)
+            .replace("{n}", &next.to_string())
+    }
+
+    /// Sets what the add popup doesn't show on a freshly created item. The
+    /// status is left to [`ProductionManager::request_status`].
+    pub fn apply_extras(&self, item: &mut Item) {
+        item.tags = self.tags.clone();
+        item.checklist = self
+            .checklist
+            .iter()
+            .map(|text| ChecklistEntry { text: text.clone(), done: false })
+            .collect();
+    }
+}
+
+/// `☑ done/total` on a card.
+pub fn render_checklist_progress(ui: &mut egui::Ui, lang: Language, checklist: &[ChecklistEntry]) {
+    if checklist.is_empty() {
+        return;
+    }
+    let done = checklist.iter().filter(|e| e.done).count();
+    let color = if done == checklist.len() {
+        egui::Color32::from_rgb(80, 170, 100)
+    } else {
+        egui::Color32::from_rgb(150, 150, 155)
+    };
+    let open: Vec<&str> = checklist.iter().filter(|e| !e.done).map(|e| e.text.as_str()).collect();
+    ui.label(egui::RichText::new(format!("☑ {}/{}", done, checklist.len())).size(13.0).color(color))
+        .on_hover_text(format!("{}\n{}", tr(lang, Msg::ChecklistLabel), open.join("\n")));
+}
+
+/// Checklist rows for the edit popup.
+pub fn render_checklist_editor(ui: &mut egui::Ui, lang: Language, checklist: &mut Vec<ChecklistEntry>, draft: &mut String) {
+    let mut remove = None;
+    for (idx, entry) in checklist.iter_mut().enumerate() {
+        ui.horizontal(|ui| {
+            ui.checkbox(&mut entry.done, "");
+            ui.add(egui::TextEdit::singleline(&mut entry.text).desired_width(260.0));
+            if ui.small_button("✖").on_hover_text(tr(lang, Msg::Delete)).clicked() {
+                remove = Some(idx);
+            }
+        });
+    }
+    if let Some(idx) = remove {
+        checklist.remove(idx);
+    }
+    ui.horizontal(|ui| {
+        let response = ui.add(egui::TextEdit::singleline(draft).desired_width(260.0).hint_text(tr(lang, Msg::ChecklistAddHint)));
+        let submitted = response.lost_focus() && ui.input(|i| i.key_pressed(egui::Key::Enter));
+        if (ui.small_button("➕").clicked() || submitted) && !draft.trim().is_empty() {
+            checklist.push(ChecklistEntry { text: draft.trim().to_string(), done: false });
+            draft.clear();
+            response.request_focus();
+        }
+    });
+}
+
+/// A template being edited, with its lists as text.
+struct TemplateDraft {
+    template: Template,
+    tags: String,
+    checklist: String,
+}
+
+impl TemplateDraft {
+    fn new(template: Template) -> Self {
+        Self {
+            tags: tags::join_tags(&template.tags),
+            checklist: template.checklist.join("\n"),
+            template,
+        }
+    }
+
+    fn finish(self) -> Template {
+        Template {
+            name: self.template.name.trim().to_string(),
+            tags: tags::parse_tags(&self.tags),
+            checklist: self.checklist.lines().map(str::trim).filter(|l| !l.is_empty()).map(str::to_string).collect(),
+            ..self.template
+        }
+    }
+}
+
+/// Working copy of the templates and category defaults.
+pub struct TemplateDialog {
+    drafts: Vec<TemplateDraft>,
+    defaults: Vec<Option<String>>,
+    selected: usize,
+}
+
+impl ProductionManager {
+    pub(crate) fn find_template(&self, id: &str) -> Option<&Template> {
+        self.data.templates.iter().find(|t| t.id == id)
+    }
+
+    /// Fills the add popup from a template, or clears it for `None`.
+    pub(crate) fn apply_template(&mut self, id: Option<String>) {
+        let cat = &self.data.categories[self.add_popup_category];
+        let prefill = id
+            .as_deref()
+            .and_then(|id| self.find_template(id))
+            .map(|t| (t.expand_title(&cat.name, cat.items.len() + 1), t.comment.clone()));
+        let (title, comment) = prefill.unwrap_or_default();
+        self.new_item_title = title;
+        self.new_item_comment = comment;
+        self.new_item_template = id.filter(|id| self.find_template(id).is_some());
+    }
+
+    /// Template picker row of the add popup.
+    pub(crate) fn render_template_picker(&mut self, ui: &mut egui::Ui) {
+        if self.data.templates.is_empty() {
+            return;
+        }
+        let selected_name = self
+            .new_item_template
+            .as_deref()
+            .and_then(|id| self.find_template(id))
+            .map_or_else(|| self.t(Msg::TemplateNone).to_string(), |t| t.name.clone());
+        let mut choice = self.new_item_template.clone();
+        ui.horizontal(|ui| {
+            ui.label(self.t(Msg::TemplateLabel));
+            egui::ComboBox::from_id_salt("add_popup_template")
+                .selected_text(selected_name)
+                .show_ui(ui, |ui| {
+                    ui.selectable_value(&mut choice, None, self.t(Msg::TemplateNone));
+                    for template in &self.data.templates {
+                        ui.selectable_value(&mut choice, Some(template.id.clone()), &template.name);
+                    }
+                });
+        });
+        if choice != self.new_item_template {
+            self.apply_template(choice);
+        }
+    }
+
+    pub(crate) fn open_template_dialog(&mut self) {
+        self.template_dialog = Some(TemplateDialog {
+            drafts: self.data.templates.iter().cloned().map(TemplateDraft::new).collect(),
+            defaults: self.data.categories.iter().map(|c| c.default_template.clone()).collect(),
+            selected: 0,
+        });
+    }
+
+    pub(crate) fn render_template_dialog(&mut self, ctx: &egui::Context) {
+        let Some(mut dialog) = self.template_dialog.take() else {
+            return;
+        };
+        let lang = self.settings.language;
+        let mut keep_open = true;
+        let mut save = false;
+
+        egui::Window::new(self.t(Msg::TemplatesTitle))
+            .id(egui::Id::new("template_dialog"))
+            .collapsible(false)
+            .resizable(false)
+            .anchor(egui::Align2::CENTER_CENTER, [0.0, 0.0])
+            .show(ctx, |ui| {
+                ui.horizontal_top(|ui| {
+                    ui.vertical(|ui| {
+                        ui.set_width(150.0);
+                        for (idx, draft) in dialog.drafts.iter().enumerate() {
+                            let name = if draft.template.name.trim().is_empty() { "…" } else { draft.template.name.as_str() };
+                            ui.selectable_value(&mut dialog.selected, idx, name);
+                        }
+                        ui.add_space(6.0);
+                        ui.horizontal(|ui| {
+                            if ui.small_button(tr(lang, Msg::TemplateAdd)).clicked() {
+                                dialog.drafts.push(TemplateDraft::new(Template::new(tr(lang, Msg::TemplateNewName).to_string())));
+                                dialog.selected = dialog.drafts.len() - 1;
+                            }
+                            let can_delete = dialog.selected < dialog.drafts.len();
+                            if ui.add_enabled(can_delete, egui::Button::new("🗑").small()).on_hover_text(tr(lang, Msg::Delete)).clicked() {
+                                let removed = dialog.drafts.remove(dialog.selected);
+                                for default in &mut dialog.defaults {
+                                    if default.as_deref() == Some(removed.template.id.as_str()) {
+                                        *default = None;
+                                    }
+                                }
+                                dialog.selected = dialog.selected.saturating_sub(1);
+                            }
+                        });
+                    });
+                    ui.separator();
+                    ui.vertical(|ui| {
+                        let Some(draft) = dialog.drafts.get_mut(dialog.selected) else {
+                            ui.label(egui::RichText::new(tr(lang, Msg::TemplatesEmpty)).color(egui::Color32::from_rgb(150, 150, 155)));
+                            return;
+                        };
+                        egui::Grid::new("template_form").num_columns(2).spacing([8.0, 6.0]).show(ui, |ui| {
+                            ui.label(tr(lang, Msg::TemplateName));
+                            ui.add_sized([260.0, 20.0], egui::TextEdit::singleline(&mut draft.template.name));
+                            ui.end_row();
+
+                            ui.label(tr(lang, Msg::TitleLabel));
+                            ui.add_sized([260.0, 20.0], egui::TextEdit::singleline(&mut draft.template.title).hint_text("{category} #{n} ({date})"));
+                            ui.end_row();
+
+                            ui.label(tr(lang, Msg::CommentLabel));
+                            ui.add(egui::TextEdit::multiline(&mut draft.template.comment).desired_width(260.0).desired_rows(3));
+                            ui.end_row();
+
+                            ui.label(tr(lang, Msg::TagsLabel));
+                            ui.add(egui::TextEdit::singleline(&mut draft.tags).desired_width(260.0).hint_text(tr(lang, Msg::TagsHint)));
+                            ui.end_row();
+
+                            ui.label(tr(lang, Msg::ChecklistLabel));
+                            ui.add(egui::TextEdit::multiline(&mut draft.checklist).desired_width(260.0).desired_rows(4).hint_text(tr(lang, Msg::TemplateChecklistHint)));
+                            ui.end_row();
+
+                            ui.label(tr(lang, Msg::StatusLabel));
+                            egui::ComboBox::from_id_salt("template_status")
+                                .selected_text(draft.template.status.name(lang))
+                                .show_ui(ui, |ui| {
+                                    for status in ItemStatus::ALL {
+                                        ui.selectable_value(&mut draft.template.status, status, status.name(lang));
+                                    }
+                                });
+                            ui.end_row();
+                        });
+                    });
+                });
+
+                ui.add_space(8.0);
+                ui.separator();
+                ui.label(tr(lang, Msg::TemplateDefaults));
+                egui::Grid::new("template_defaults").num_columns(2).spacing([8.0, 4.0]).show(ui, |ui| {
+                    for (cat, default) in self.data.categories.iter().zip(dialog.defaults.iter_mut()) {
+                        ui.label(&cat.name);
+                        let name = default
+                            .as_deref()
+                            .and_then(|id| dialog.drafts.iter().find(|d| d.template.id == id))
+                            .map_or(tr(lang, Msg::TemplateNone), |d| d.template.name.as_str());
+                        egui::ComboBox::from_id_salt(("template_default", &cat.name))
+                            .selected_text(name)
+                            .show_ui(ui, |ui| {
+                                ui.selectable_value(default, None, tr(lang, Msg::TemplateNone));
+                                for draft in &dialog.drafts {
+                                    ui.selectable_value(default, Some(draft.template.id.clone()), &draft.template.name);
+                                }
+                            });
+                        ui.end_row();
+                    }
+                });
+
+                ui.add_space(12.0);
+                ui.horizontal(|ui| {
+                    if ui.button(tr(lang, Msg::Cancel)).clicked() {
+                        keep_open = false;
+                    }
+                    ui.add_space(20.0);
+                    let valid = dialog.drafts.iter().all(|d| !d.template.name.trim().is_empty());
+                    if ui.add_enabled(valid, egui::Button::new(tr(lang, Msg::Save))).clicked() {
+                        save = true;
+                    }
+                });
+            });
+
+        if save {
+            self.data.templates = dialog.drafts.into_iter().map(TemplateDraft::finish).collect();
+            for (cat, default) in self.data.categories.iter_mut().zip(dialog.defaults) {
+                cat.default_template = default;
+            }
+            self.save_data();
+            self.show_status(self.t(Msg::Saved));
+        } else if keep_open {
+            self.template_dialog = Some(dialog);
+        }
+    }
+}
@@ -0,0 +1,404 @@
+//! Timeline (Gantt) of planned start and end dates, grouped by category.
+//! Bars can be dragged to move them and resized by their edges.
+
+use crate::i18n::Msg;
+use crate::status::{today, ItemStatus};
+use crate::{AppData, ProductionManager};
+use chrono::{Datelike, Duration, NaiveDate};
+use eframe::egui;
+use serde::{Deserialize, Serialize};
+use std::fmt::Write as _;
+use std::fs;
+
+const LABEL_WIDTH: f32 = 200.0;
+const HEADER_HEIGHT: f32 = 28.0;
+const ROW_HEIGHT: f32 = 26.0;
+/// Width of the grab zone at each end of a bar.
+const HANDLE_WIDTH: f32 = 6.0;
+/// Length given to an item scheduled by clicking its empty row.
+const DEFAULT_SPAN_DAYS: i64 = 6;
+
+#[derive(Serialize, Deserialize, Clone, Copy, Debug, PartialEq, Eq, Default)]
+pub enum TimelineZoom {
+    Week,
+    #[default]
+    Month,
+    Quarter,
+}
+
+impl TimelineZoom {
+    const ALL: [TimelineZoom; 3] = [TimelineZoom::Week, TimelineZoom::Month, TimelineZoom::Quarter];
+
+    fn pixels_per_day(self) -> f32 {
+        match self {
+            TimelineZoom::Week => 36.0,
+            TimelineZoom::Month => 10.0,
+            TimelineZoom::Quarter => 3.5,
+        }
+    }
+
+    fn msg(self) -> Msg {
+        match self {
+            TimelineZoom::Week => Msg::TimelineWeek,
+            TimelineZoom::Month => Msg::TimelineMonth,
+            TimelineZoom::Quarter => Msg::TimelineQuarter,
+        }
+    }
+
+    /// Grid lines with their labels.
+    fn ticks(self, first: NaiveDate, last: NaiveDate) -> Vec<(NaiveDate, String)> {
+        let mut ticks = Vec::new();
+        let mut day = first;
+        while day <= last {
+            let label = match self {
+                TimelineZoom::Week if day.weekday().num_days_from_monday() == 0 => Some(day.format("%m/%d").to_string()),
+                TimelineZoom::Week => Some(day.day().to_string()),
+                TimelineZoom::Month if day.weekday().num_days_from_monday() == 0 => Some(day.format("%m/%d").to_string()),
+                TimelineZoom::Quarter if day.day() == 1 => Some(day.format("%Y-%m").to_string()),
+                _ => None,
+            };
+            if let Some(label) = label {
+                ticks.push((day, label));
+            }
+            day += Duration::days(1);
+        }
+        ticks
+    }
+}
+
+#[derive(Clone, Copy, Debug, PartialEq)]
+enum DragMode {
+    Move,
+    ResizeStart,
+    ResizeEnd,
+}
+
+/// Bar being dragged and how far it has moved so far.
+pub struct TimelineDrag {
+    item_id: String,
+    mode: DragMode,
+    offset: f32,
+}
+
+enum Row {
+    Category(usize),
+    Item(usize, usize),
+}
+
+/// Dates of a bar, if the item is scheduled. A missing end (or start)
+/// makes a one-day bar.
+fn span(start: Option<NaiveDate>, end: Option<NaiveDate>) -> Option<(NaiveDate, NaiveDate)> {
+    match (start, end) {
+        (Some(s), Some(e)) => Some((s.min(e), s.max(e))),
+        (Some(d), None) | (None, Some(d)) => Some((d, d)),
+        (None, None) => None,
+    }
+}
+
+fn shifted(mode: DragMode, (start, end): (NaiveDate, NaiveDate), days: i64) -> (NaiveDate, NaiveDate) {
+    let delta = Duration::days(days);
+    match mode {
+        DragMode::Move => (start + delta, end + delta),
+        DragMode::ResizeStart => ((start + delta).min(end), end),
+        DragMode::ResizeEnd => (start, (end + delta).max(start)),
+    }
+}
+
+fn rows(data: &AppData) -> Vec<Row> {
+    let mut rows = Vec::new();
+    for (cat_idx, cat) in data.categories.iter().enumerate() {
+        rows.push(Row::Category(cat_idx));
+        rows.extend((0..cat.items.len()).map(|item_idx| Row::Item(cat_idx, item_idx)));
+    }
+    rows
+}
+
+/// Visible date range: every scheduled item and today, with some margin,
+/// starting on a Monday.
+fn date_range(data: &AppData) -> (NaiveDate, NaiveDate) {
+    let today = today();
+    let (mut first, mut last) = (today - Duration::days(30), today + Duration::days(90));
+    for item in data.categories.iter().flat_map(|c| c.items.iter()) {
+        if let Some((start, end)) = span(item.start_date, item.end_date) {
+            first = first.min(start - Duration::days(14));
+            last = last.max(end + Duration::days(30));
+        }
+    }
+    first -= Duration::days(first.weekday().num_days_from_monday() as i64);
+    (first, last)
+}
+
+fn xml_escape(text: &str) -> String {
+    text.replace('&', "&amp;").replace('<', "&lt;").replace('>', "&gt;").replace('"', "&quot;")
+}
+
+fn svg_color(color: egui::Color32) -> String {
+    format!("#{:02x}{:02x}{:02x}", color.r(), color.g(), color.b())
+}
+
+/// The timeline as a standalone SVG image, laid out like the view.
+fn to_svg(data: &AppData, zoom: TimelineZoom) -> String {
+    let (first, last) = date_range(data);
+    let ppd = zoom.pixels_per_day();
+    let rows = rows(data);
+    let width = LABEL_WIDTH + ((last - first).num_days() + 1) as f32 * ppd;
+    let height = HEADER_HEIGHT + rows.len() as f32 * ROW_HEIGHT;
+    let x_of = |date: NaiveDate| LABEL_WIDTH + (date - first).num_days() as f32 * ppd;
+
+    let mut svg = String::new();
+    let _ = writeln!(
+        svg,
+        r#"<svg xmlns="http://www.w3.org/2000/svg" width="{width}" height="{height}" viewBox="0 0 {width} {height}" font-family="sans-serif" font-size="12">"#
+    );
+    let _ = writeln!(svg, r##"<rect width="100%" height="100%" fill="#28282d"/>"##);
+    for (date, label) in zoom.ticks(first, last) {
+        let x = x_of(date);
+        let _ = writeln!(svg, r##"<line x1="{x}" y1="0" x2="{x}" y2="{height}" stroke="#3c3c42"/>"##);
+        let _ = writeln!(svg, r##"<text x="{}" y="18" fill="#96969b">{}</text>"##, x + 3.0, xml_escape(&label));
+    }
+    let x = x_of(today()) + ppd / 2.0;
+    let _ = writeln!(svg, r##"<line x1="{x}" y1="0" x2="{x}" y2="{height}" stroke="#dc6464" stroke-width="1.5"/>"##);
+
+    for (row_idx, row) in rows.iter().enumerate() {
+        let y = HEADER_HEIGHT + row_idx as f32 * ROW_HEIGHT;
+        match *row {
+            Row::Category(cat_idx) => {
+                let cat = &data.categories[cat_idx];
+                let _ = writeln!(svg, r##"<rect x="0" y="{y}" width="{width}" height="{ROW_HEIGHT}" fill="#323238"/>"##);
+                let _ = writeln!(
+                    svg,
+                    r#"<text x="8" y="{}" fill="{}" font-weight="bold">{}</text>"#,
+                    y + 17.0,
+                    svg_color(cat.color(cat_idx)),
+                    xml_escape(&cat.name)
+                );
+            }
+            Row::Item(cat_idx, item_idx) => {
+                let cat = &data.categories[cat_idx];
+                let item = &cat.items[item_idx];
+                let _ = writeln!(svg, r##"<text x="20" y="{}" fill="#dcdce1">{}</text>"##, y + 17.0, xml_escape(&item.title));
+                if let Some((start, end)) = span(item.start_date, item.end_date) {
+                    let x = x_of(start);
+                    let w = x_of(end + Duration::days(1)) - x;
+                    let opacity = if item.status == ItemStatus::Done { 0.5 } else { 1.0 };
+                    let _ = writeln!(
+                        svg,
+                        r#"<rect x="{x}" y="{}" width="{w}" height="{}" rx="4" fill="{}" fill-opacity="{opacity}"/>"#,
+                        y + 4.0,
+                        ROW_HEIGHT - 8.0,
+                        svg_color(cat.color(cat_idx))
+                    );
+                }
+            }
+        }
+    }
+    svg.push_str("</svg>\n");
+    svg
+}
+
+impl ProductionManager {
+    fn export_timeline_svg(&mut self) {
+        if let Some(path) = rfd::FileDialog::new()
+            .set_file_name("timeline.svg")
+            .add_filter("SVG", &["svg"])
+            .save_file()
+        {
+            if fs::write(&path, to_svg(&self.data, self.settings.timeline_zoom)).is_ok() {
+                self.show_status(self.t(Msg::Exported));
+            }
+        }
+    }
+
+    fn render_timeline_toolbar(&mut self, ui: &mut egui::Ui) {
+        ui.horizontal(|ui| {
+            let zoom = self.settings.timeline_zoom;
+            let mut selected = zoom;
+            for option in TimelineZoom::ALL {
+                ui.selectable_value(&mut selected, option, self.t(option.msg()));
+            }
+            if selected != zoom {
+                self.settings.timeline_zoom = selected;
+                self.save_settings();
+            }
+            ui.add_space(12.0);
+            ui.label(egui::RichText::new(self.t(Msg::TimelineHint)).size(13.0).color(egui::Color32::from_rgb(150, 150, 155)));
+            ui.with_layout(egui::Layout::right_to_left(egui::Align::Center), |ui| {
+                if ui.button(self.t(Msg::ExportSvg)).clicked() {
+                    self.export_timeline_svg();
+                }
+            });
+        });
+    }
+
+    pub(crate) fn render_timeline(&mut self, ui: &mut egui::Ui) {
+        self.render_timeline_toolbar(ui);
+        ui.add_space(6.0);
+
+        let zoom = self.settings.timeline_zoom;
+        let ppd = zoom.pixels_per_day();
+        let (first, last) = date_range(&self.data);
+        let rows = rows(&self.data);
+        let chart_width = ((last - first).num_days() + 1) as f32 * ppd;
+        let height = HEADER_HEIGHT + rows.len() as f32 * ROW_HEIGHT;
+        let muted = egui::Color32::from_rgb(150, 150, 155);
+        let grid = egui::Color32::from_rgb(60, 60, 66);
+
+        let data = &self.data;
+        let drag = &mut self.timeline_drag;
+        let mut commit = None;
+        let mut schedule = None;
+        let mut open = None;
+
+        egui::ScrollArea::vertical().id_salt("timeline_v").auto_shrink([false, false]).show(ui, |ui| {
+            ui.horizontal_top(|ui| {
+                ui.spacing_mut().item_spacing.x = 0.0;
+
+                let (labels, _) = ui.allocate_exact_size(egui::vec2(LABEL_WIDTH, height), egui::Sense::hover());
+                let painter = ui.painter_at(labels);
+                for (row_idx, row) in rows.iter().enumerate() {
+                    let y = labels.top() + HEADER_HEIGHT + row_idx as f32 * ROW_HEIGHT;
+                    let row_rect = egui::Rect::from_min_size(egui::pos2(labels.left(), y), egui::vec2(LABEL_WIDTH, ROW_HEIGHT));
+                    match *row {
+                        Row::Category(cat_idx) => {
+                            let cat = &data.categories[cat_idx];
+                            painter.rect_filled(row_rect, 0.0, egui::Color32::from_rgb(50, 50, 56));
+                            painter.text(row_rect.left_center() + egui::vec2(8.0, 0.0), egui::Align2::LEFT_CENTER, &cat.name, egui::FontId::proportional(14.0), cat.color(cat_idx));
+                        }
+                        Row::Item(cat_idx, item_idx) => {
+                            let item = &data.categories[cat_idx].items[item_idx];
+                            let response = ui.interact(row_rect, ui.id().with(("timeline_label", &item.id)), egui::Sense::click());
+                            if response.double_clicked() {
+                                open = Some((cat_idx, item_idx));
+                            }
+                            let text_rect = row_rect.shrink2(egui::vec2(20.0, 0.0));
+                            ui.painter_at(text_rect).text(text_rect.left_center(), egui::Align2::LEFT_CENTER, &item.title, egui::FontId::proportional(13.0), egui::Color32::from_rgb(220, 220, 225));
+                        }
+                    }
+                }
+
+                egui::ScrollArea::horizontal().id_salt("timeline_h").show(ui, |ui| {
+                    let (chart, _) = ui.allocate_exact_size(egui::vec2(chart_width, height), egui::Sense::hover());
+                    let painter = ui.painter_at(chart);
+                    let x_of = |date: NaiveDate| chart.left() + (date - first).num_days() as f32 * ppd;
+                    let day_at = |x: f32| first + Duration::days(((x - chart.left()) / ppd).floor() as i64);
+
+                    for (row_idx, row) in rows.iter().enumerate() {
+                        if let Row::Category(_) = row {
+                            let y = chart.top() + HEADER_HEIGHT + row_idx as f32 * ROW_HEIGHT;
+                            let rect = egui::Rect::from_min_size(egui::pos2(chart.left(), y), egui::vec2(chart_width, ROW_HEIGHT));
+                            painter.rect_filled(rect, 0.0, egui::Color32::from_rgb(50, 50, 56));
+                        }
+                    }
+                    for (date, label) in zoom.ticks(first, last) {
+                        let x = x_of(date);
+                        painter.line_segment([egui::pos2(x, chart.top()), egui::pos2(x, chart.bottom())], (1.0, grid));
+                        painter.text(egui::pos2(x + 3.0, chart.top() + HEADER_HEIGHT / 2.0), egui::Align2::LEFT_CENTER, label, egui::FontId::proportional(11.0), muted);
+                    }
+                    let today_x = x_of(today()) + ppd / 2.0;
+                    painter.line_segment([egui::pos2(today_x, chart.top()), egui::pos2(today_x, chart.bottom())], (1.5, egui::Color32::from_rgb(220, 100, 100)));
+
+                    for (row_idx, row) in rows.iter().enumerate() {
+                        let Row::Item(cat_idx, item_idx) = *row else {
+                            continue;
+                        };
+                        let cat = &data.categories[cat_idx];
+                        let item = &cat.items[item_idx];
+                        let y = chart.top() + HEADER_HEIGHT + row_idx as f32 * ROW_HEIGHT;
+                        let row_rect = egui::Rect::from_min_size(egui::pos2(chart.left(), y), egui::vec2(chart_width, ROW_HEIGHT));
+
+                        let Some(mut dates) = span(item.start_date, item.end_date) else {
+                            // Unscheduled: click the row to put a bar there.
+                            let response = ui.interact(row_rect, ui.id().with(("timeline_row", &item.id)), egui::Sense::click());
+                            if response.hovered() {
+                                painter.rect_filled(row_rect, 0.0, egui::Color32::from_white_alpha(6));
+                            }
+                            if response.clicked() {
+                                if let Some(pos) = response.interact_pointer_pos() {
+                                    schedule = Some((cat_idx, item_idx, day_at(pos.x)));
+                                }
+                            }
+                            continue;
+                        };
+
+                        if let Some(active) = drag.as_ref().filter(|d| d.item_id == item.id) {
+                            dates = shifted(active.mode, dates, (active.offset / ppd).round() as i64);
+                        }
+                        let bar = egui::Rect::from_min_max(
+                            egui::pos2(x_of(dates.0), y + 4.0),
+                            egui::pos2(x_of(dates.1 + Duration::days(1)), y + ROW_HEIGHT - 4.0),
+                        );
+                        let mut color = cat.color(cat_idx);
+                        if item.status == ItemStatus::Done {
+                            color = color.gamma_multiply(0.5);
+                        }
+                        painter.rect_filled(bar, 4.0, color);
+                        painter
+                            .with_clip_rect(bar.intersect(chart))
+                            .text(bar.left_center() + egui::vec2(6.0, 0.0), egui::Align2::LEFT_CENTER, &item.title, egui::FontId::proportional(12.0), egui::Color32::WHITE);
+
+                        let handle_width = HANDLE_WIDTH.min(bar.width() / 3.0);
+                        let parts = [
+                            (DragMode::Move, bar, "timeline_bar"),
+                            (DragMode::ResizeStart, egui::Rect::from_min_max(bar.min, egui::pos2(bar.left() + handle_width, bar.bottom())), "timeline_start"),
+                            (DragMode::ResizeEnd, egui::Rect::from_min_max(egui::pos2(bar.right() - handle_width, bar.top()), bar.max), "timeline_end"),
+                        ];
+                        for (mode, rect, salt) in parts {
+                            let response = ui.interact(rect, ui.id().with((salt, &item.id)), egui::Sense::click_and_drag());
+                            let hover_cursor = if mode == DragMode::Move { egui::CursorIcon::Grab } else { egui::CursorIcon::ResizeHorizontal };
+                            if response.hovered() || response.dragged() {
+                                ui.ctx().set_cursor_icon(hover_cursor);
+                            }
+                            if mode == DragMode::Move {
+                                let text = format!("{}\n{} – {}", item.title, dates.0.format("%Y-%m-%d"), dates.1.format("%Y-%m-%d"));
+                                if response.double_clicked() {
+                                    open = Some((cat_idx, item_idx));
+                                }
+                                if drag.is_none() {
+                                    response.clone().on_hover_text(text);
+                                }
+                            }
+                            if response.drag_started() {
+                                *drag = Some(TimelineDrag {
+                                    item_id: item.id.clone(),
+                                    mode,
+                                    offset: 0.0,
+                                });
+                            }
+                            if let Some(active) = drag.as_mut().filter(|d| d.item_id == item.id && d.mode == mode) {
+                                if response.dragged() {
+                                    active.offset += response.drag_delta().x;
+                                }
+                                if response.drag_stopped() {
+                                    let days = (active.offset / ppd).round() as i64;
+                                    let original = span(item.start_date, item.end_date).unwrap_or(dates);
+                                    commit = Some((cat_idx, item_idx, shifted(mode, original, days)));
+                                    *drag = None;
+                                }
+                            }
+                        }
+                    }
+                });
+            });
+        });
+
+        if let Some((cat_idx, item_idx, (start, end))) = commit {
+            let item = &mut self.data.categories[cat_idx].items[item_idx];
+            if (item.start_date, item.end_date) != (Some(start), Some(end)) {
+                item.start_date = Some(start);
+                item.end_date = Some(end);
+                item.touch();
+                self.save_data();
+            }
+        }
+        if let Some((cat_idx, item_idx, day)) = schedule {
+            let item = &mut self.data.categories[cat_idx].items[item_idx];
+            item.start_date = Some(day);
+            item.end_date = Some(day + Duration::days(DEFAULT_SPAN_DAYS));
+            item.touch();
+            self.save_data();
+        }
+        if let Some((cat_idx, item_idx)) = open {
+            self.apply_card_action(cat_idx, item_idx, crate::CardAction::Edit);
+        }
+    }
+}
@@ -0,0 +1,465 @@
+//! Time tracking: start/stop timers and manual entries on items, with a
+//! report over a date range.
+
+use crate::i18n::Msg;
+use crate::status::parse_date;
+use crate::{Item, ProductionManager};
+use chrono::{DateTime, Datelike, Duration, Local, NaiveDate, NaiveTime, TimeZone};
+use eframe::egui;
+use serde::{Deserialize, Serialize};
+use std::fs;
+
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq)]
+pub struct TimeEntry {
+    pub start: DateTime<Local>,
+    /// `None` while the timer is running. Stored with the data, so a running
+    /// timer keeps running across restarts.
+    #[serde(default)]
+    pub end: Option<DateTime<Local>>,
+    #[serde(default, skip_serializing_if = "String::is_empty")]
+    pub note: String,
+}
+
+impl TimeEntry {
+    pub fn is_running(&self) -> bool {
+        self.end.is_none()
+    }
+
+    /// Seconds of this entry that fall inside `[from, to)`.
+    fn seconds_within(&self, from: DateTime<Local>, to: DateTime<Local>, now: DateTime<Local>) -> i64 {
+        let end = self.end.unwrap_or(now).min(to);
+        let start = self.start.max(from);
+        (end - start).num_seconds().max(0)
+    }
+
+    fn seconds(&self, now: DateTime<Local>) -> i64 {
+        (self.end.unwrap_or(now) - self.start).num_seconds().max(0)
+    }
+}
+
+pub fn total_seconds(entries: &[TimeEntry]) -> i64 {
+    let now = Local::now();
+    entries.iter().map(|e| e.seconds(now)).sum()
+}
+
+/// `3h 05m`, or `45m` under an hour.
+pub fn format_duration(seconds: i64) -> String {
+    let minutes = seconds / 60;
+    if minutes >= 60 {
+        format!("{}h {:02}m", minutes / 60, minutes % 60)
+    } else {
+        format!("{}m", minutes)
+    }
+}
+
+/// `1:02:03` for the running timer.
+fn format_clock(seconds: i64) -> String {
+    format!("{}:{:02}:{:02}", seconds / 3600, seconds / 60 % 60, seconds % 60)
+}
+
+fn local_time(date: NaiveDate, time: NaiveTime) -> Option<DateTime<Local>> {
+    Local.from_local_datetime(&date.and_time(time)).earliest()
+}
+
+fn csv_field(value: &str) -> String {
+    if value.contains([',', '"', '\n', '\r']) {
+        format!("\"{}\"", value.replace('"', "\"\""))
+    } else {
+        value.to_string()
+    }
+}
+
+/// Input row for adding a manual entry in the edit popup.
+pub struct TimeDraft {
+    date: String,
+    start: String,
+    end: String,
+    note: String,
+}
+
+impl Default for TimeDraft {
+    fn default() -> Self {
+        Self {
+            date: Local::now().format("%Y-%m-%d").to_string(),
+            start: String::new(),
+            end: String::new(),
+            note: String::new(),
+        }
+    }
+}
+
+impl TimeDraft {
+    fn entry(&self) -> Option<TimeEntry> {
+        let date = parse_date(&self.date)?;
+        let start = NaiveTime::parse_from_str(self.start.trim(), "%H:%M").ok()?;
+        let end = NaiveTime::parse_from_str(self.end.trim(), "%H:%M").ok()?;
+        // An end before the start runs past midnight.
+        let end_date = if end <= start { date.succ_opt()? } else { date };
+        Some(TimeEntry {
+            start: local_time(date, start)?,
+            end: Some(local_time(end_date, end)?),
+            note: self.note.trim().to_string(),
+        })
+    }
+}
+
+/// Open report window and its date range (inclusive).
+pub struct TimeReport {
+    from: String,
+    to: String,
+}
+
+impl TimeReport {
+    fn this_week() -> Self {
+        let today = Local::now().date_naive();
+        let monday = today - Duration::days(today.weekday().num_days_from_monday() as i64);
+        Self {
+            from: monday.format("%Y-%m-%d").to_string(),
+            to: today.format("%Y-%m-%d").to_string(),
+        }
+    }
+
+    fn this_month() -> Self {
+        let today = Local::now().date_naive();
+        Self {
+            from: today.with_day(1).unwrap_or(today).format("%Y-%m-%d").to_string(),
+            to: today.format("%Y-%m-%d").to_string(),
+        }
+    }
+
+    fn range(&self) -> Option<(DateTime<Local>, DateTime<Local>)> {
+        let from = local_time(parse_date(&self.from)?, NaiveTime::MIN)?;
+        let to = local_time(parse_date(&self.to)?.succ_opt()?, NaiveTime::MIN)?;
+        (from < to).then_some((from, to))
+    }
+}
+
+/// Tracked time of one item within the report range.
+struct ReportRow<'a> {
+    category: &'a str,
+    item: &'a Item,
+    seconds: i64,
+}
+
+impl ProductionManager {
+    /// Category and item index of the running timer.
+    pub(crate) fn running_timer(&self) -> Option<(usize, usize)> {
+        self.data.categories.iter().enumerate().find_map(|(cat_idx, cat)| {
+            cat.items
+                .iter()
+                .position(|item| item.time_entries.iter().any(TimeEntry::is_running))
+                .map(|item_idx| (cat_idx, item_idx))
+        })
+    }
+
+    fn stop_running_timers(&mut self) {
+        let now = Local::now();
+        for item in self.data.categories.iter_mut().flat_map(|c| c.items.iter_mut()) {
+            for entry in item.time_entries.iter_mut().filter(|e| e.is_running()) {
+                entry.end = Some(now);
+            }
+        }
+    }
+
+    /// Starts the timer of an item, stopping any other; stops it if it is
+    /// the one running.
+    pub(crate) fn toggle_timer(&mut self, cat_idx: usize, item_idx: usize) {
+        let was_running = self.running_timer() == Some((cat_idx, item_idx));
+        self.stop_running_timers();
+        if !was_running {
+            if let Some(item) = self.data.categories[cat_idx].items.get_mut(item_idx) {
+                item.time_entries.push(TimeEntry {
+                    start: Local::now(),
+                    end: None,
+                    note: String::new(),
+                });
+            }
+        }
+        self.save_data();
+    }
+
+    /// Running timer in the header, with a stop button.
+    pub(crate) fn render_timer_indicator(&mut self, ui: &mut egui::Ui) {
+        let Some((cat_idx, item_idx)) = self.running_timer() else {
+            return;
+        };
+        let item = &self.data.categories[cat_idx].items[item_idx];
+        let elapsed = item
+            .time_entries
+            .iter()
+            .find(|e| e.is_running())
+            .map_or(0, |e| e.seconds(Local::now()));
+        let title = item.title.clone();
+
+        if ui.button("⏹").on_hover_text(self.t(Msg::StopTimer)).clicked() {
+            self.toggle_timer(cat_idx, item_idx);
+        }
+        ui.label(
+            egui::RichText::new(format!("⏱ {}  {}", format_clock(elapsed), title))
+                .color(egui::Color32::from_rgb(230, 160, 90)),
+        );
+    }
+
+    /// Time entries of the item being edited. Changes apply immediately so
+    /// they don't race the header timer.
+    pub(crate) fn render_time_entries(&mut self, ui: &mut egui::Ui) {
+        let cat_idx = self.edit_category;
+        let Some(item_idx) = self.data.categories[cat_idx].items.iter().position(|i| i.id == self.edit_item_id) else {
+            return;
+        };
+        let now = Local::now();
+        let entries = &self.data.categories[cat_idx].items[item_idx].time_entries;
+        let total = total_seconds(entries);
+
+        ui.horizontal(|ui| {
+            ui.label(self.t(Msg::TimeEntriesLabel));
+            ui.strong(format_duration(total));
+        });
+
+        let mut remove = None;
+        egui::ScrollArea::vertical()
+            .id_salt("time_entries")
+            .max_height(120.0)
+            .show(ui, |ui| {
+                for (idx, entry) in entries.iter().enumerate().rev() {
+                    ui.horizontal(|ui| {
+                        let end = match entry.end {
+                            Some(end) => end.format("%H:%M").to_string(),
+                            None => "…".to_string(),
+                        };
+                        ui.label(format!("{}–{}", entry.start.format("%Y-%m-%d %H:%M"), end));
+                        ui.label(egui::RichText::new(format_duration(entry.seconds(now))).color(egui::Color32::from_rgb(150, 150, 155)));
+                        if !entry.note.is_empty() {
+                            ui.label(&entry.note);
+                        }
+                        if ui.small_button("✕").on_hover_text(self.t(Msg::Delete)).clicked() {
+                            remove = Some(idx);
+                        }
+                    });
+                }
+            });
+
+        let note_hint = self.t(Msg::TimeNote);
+        let add_hint = self.t(Msg::AddTimeEntry);
+        let draft = &mut self.time_draft;
+        let mut add = None;
+        ui.horizontal(|ui| {
+            ui.add(egui::TextEdit::singleline(&mut draft.date).desired_width(80.0).hint_text("YYYY-MM-DD"));
+            ui.add(egui::TextEdit::singleline(&mut draft.start).desired_width(40.0).hint_text("09:00"));
+            ui.label("–");
+            ui.add(egui::TextEdit::singleline(&mut draft.end).desired_width(40.0).hint_text("10:30"));
+            ui.add(egui::TextEdit::singleline(&mut draft.note).desired_width(90.0).hint_text(note_hint));
+            let entry = draft.entry();
+            if ui.add_enabled(entry.is_some(), egui::Button::new("➕").small()).on_hover_text(add_hint).clicked() {
+                add = entry;
+            }
+        });
+
+        if remove.is_some() || add.is_some() {
+            let entries = &mut self.data.categories[cat_idx].items[item_idx].time_entries;
+            if let Some(idx) = remove {
+                entries.remove(idx);
+            }
+            if let Some(entry) = add {
+                entries.push(entry);
+                entries.sort_by_key(|e| e.start);
+                self.time_draft.start.clear();
+                self.time_draft.end.clear();
+                self.time_draft.note.clear();
+            }
+            self.save_data();
+        }
+    }
+
+    pub(crate) fn open_time_report(&mut self) {
+        self.time_report = Some(TimeReport::this_month());
+    }
+
+    fn report_rows(&self, from: DateTime<Local>, to: DateTime<Local>) -> Vec<ReportRow<'_>> {
+        let now = Local::now();
+        self.data
+            .categories
+            .iter()
+            .flat_map(|cat| cat.items.iter().map(move |item| (cat, item)))
+            .map(|(cat, item)| ReportRow {
+                category: &cat.name,
+                item,
+                seconds: item.time_entries.iter().map(|e| e.seconds_within(from, to, now)).sum(),
+            })
+            .filter(|row| row.seconds > 0)
+            .collect()
+    }
+
+    /// One line per entry, with the seconds clipped to the range.
+    fn report_csv(&self, from: DateTime<Local>, to: DateTime<Local>) -> String {
+        let now = Local::now();
+        let header = [
+            self.t(Msg::CsvDate),
+            self.t(Msg::CsvCategory),
+            self.t(Msg::CsvItem),
+            self.t(Msg::CsvStart),
+            self.t(Msg::CsvEnd),
+            self.t(Msg::CsvMinutes),
+            self.t(Msg::TimeNote),
+        ];
+        // BOM so spreadsheet apps detect UTF-8.
+        let mut csv = format!("\u{feff}{}\r\n", header.join(","));
+        for row in self.report_rows(from, to) {
+            for entry in &row.item.time_entries {
+                let seconds = entry.seconds_within(from, to, now);
+                if seconds == 0 {
+                    continue;
+                }
+                let end = entry.end.map(|e| e.format("%H:%M").to_string()).unwrap_or_default();
+                let fields = [
+                    entry.start.format("%Y-%m-%d").to_string(),
+                    csv_field(row.category),
+                    csv_field(&row.item.title),
+                    entry.start.format("%H:%M").to_string(),
+                    end,
+                    format!("{:.1}", seconds as f64 / 60.0),
+                    csv_field(&entry.note),
+                ];
+                csv.push_str(&fields.join(","));
+                csv.push_str("\r\n");
+            }
+        }
+        csv
+    }
+
+    fn report_markdown(&self, report: &TimeReport, from: DateTime<Local>, to: DateTime<Local>) -> String {
+        let rows = self.report_rows(from, to);
+        let mut md = format!("# {} ({} – {})\n\n", self.t(Msg::TimeReportTitle), report.from.trim(), report.to.trim());
+        md.push_str(&format!("| {} | {} | {} |\n|---|---|---:|\n", self.t(Msg::CsvCategory), self.t(Msg::CsvItem), self.t(Msg::TimeTotal)));
+        for row in &rows {
+            md.push_str(&format!(
+                "| {} | {} | {} |\n",
+                row.category.replace('|', "\\|"),
+                row.item.title.replace('|', "\\|"),
+                format_duration(row.seconds)
+            ));
+        }
+        let total: i64 = rows.iter().map(|r| r.seconds).sum();
+        md.push_str(&format!("| **{}** | | **{}** |\n", self.t(Msg::TimeTotal), format_duration(total)));
+        md
+    }
+
+    fn export_report(&mut self, report: &TimeReport, csv: bool) {
+        let Some((from, to)) = report.range() else {
+            return;
+        };
+        let (name, filter, ext) = if csv { ("time-report.csv", "CSV", "csv") } else { ("time-report.md", "Markdown", "md") };
+        if let Some(path) = rfd::FileDialog::new().set_file_name(name).add_filter(filter, &[ext]).save_file() {
+            let content = if csv { self.report_csv(from, to) } else { self.report_markdown(report, from, to) };
+            if fs::write(&path, content).is_ok() {
+                self.show_status(self.t(Msg::Exported));
+            }
+        }
+    }
+
+    pub(crate) fn render_time_report(&mut self, ctx: &egui::Context) {
+        let Some(mut report) = self.time_report.take() else {
+            return;
+        };
+        let mut keep_open = true;
+        let mut export = None;
+
+        egui::Window::new(self.t(Msg::TimeReportTitle))
+            .id(egui::Id::new("time_report"))
+            .collapsible(false)
+            .resizable(false)
+            .anchor(egui::Align2::CENTER_CENTER, [0.0, 0.0])
+            .show(ctx, |ui| {
+                ui.horizontal(|ui| {
+                    ui.label(self.t(Msg::TimeReportRange));
+                    ui.add(egui::TextEdit::singleline(&mut report.from).desired_width(90.0).hint_text("YYYY-MM-DD"));
+                    ui.label("–");
+                    ui.add(egui::TextEdit::singleline(&mut report.to).desired_width(90.0).hint_text("YYYY-MM-DD"));
+                    if ui.small_button(self.t(Msg::ThisWeek)).clicked() {
+                        report = TimeReport::this_week();
+                    }
+                    if ui.small_button(self.t(Msg::ThisMonth)).clicked() {
+                        report = TimeReport::this_month();
+                    }
+                });
+
+                ui.add_space(8.0);
+
+                let range = report.range();
+                match range {
+                    Some((from, to)) => {
+                        let rows = self.report_rows(from, to);
+                        egui::ScrollArea::vertical().max_height(320.0).show(ui, |ui| {
+                            egui::Grid::new("time_report_grid").num_columns(2).striped(true).spacing([24.0, 4.0]).show(ui, |ui| {
+                                let mut current_category = None;
+                                for row in &rows {
+                                    if current_category != Some(row.category) {
+                                        current_category = Some(row.category);
+                                        let category_total: i64 = rows.iter().filter(|r| r.category == row.category).map(|r| r.seconds).sum();
+                                        ui.strong(row.category);
+                                        ui.strong(format_duration(category_total));
+                                        ui.end_row();
+                                    }
+                                    ui.label(format!("    {}", row.item.title));
+                                    ui.label(format_duration(row.seconds));
+                                    ui.end_row();
+                                }
+                                let total: i64 = rows.iter().map(|r| r.seconds).sum();
+                                ui.strong(self.t(Msg::TimeTotal));
+                                ui.strong(format_duration(total));
+                                ui.end_row();
+                            });
+                        });
+                    }
+                    None => {
+                        ui.label(egui::RichText::new(self.t(Msg::InvalidDateRange)).color(egui::Color32::from_rgb(220, 100, 100)));
+                    }
+                }
+
+                ui.add_space(12.0);
+
+                ui.horizontal(|ui| {
+                    if ui.button(self.t(Msg::Close)).clicked() {
+                        keep_open = false;
+                    }
+                    ui.add_space(20.0);
+                    if ui.add_enabled(range.is_some(), egui::Button::new("CSV")).on_hover_text(self.t(Msg::ExportReportHint)).clicked() {
+                        export = Some(true);
+                    }
+                    if ui.add_enabled(range.is_some(), egui::Button::new("Markdown")).on_hover_text(self.t(Msg::ExportReportHint)).clicked() {
+                        export = Some(false);
+                    }
+                });
+            });
+
+        if let Some(csv) = export {
+            self.export_report(&report, csv);
+        }
+        if keep_open {
+            self.time_report = Some(report);
+        }
+    }
+}
+
+/// Total tracked time of a category, for its column header. Finished
+/// entries are added up once; running timers count up on every read.
+pub struct CategoryTime {
+    finished: i64,
+    running: Vec<DateTime<Local>>,
+}
+
+impl CategoryTime {
+    pub fn of(items: &[Item]) -> Self {
+        let entries = items.iter().flat_map(|i| &i.time_entries);
+        let now = Local::now();
+        Self {
+            finished: entries.clone().filter(|e| !e.is_running()).map(|e| e.seconds(now)).sum(),
+            running: entries.filter(|e| e.is_running()).map(|e| e.start).collect(),
+        }
+    }
+
+    pub fn seconds(&self) -> i64 {
+        let now = Local::now();
+        self.finished + self.running.iter().map(|&start| (now - start).num_seconds().max(0)).sum::<i64>()
+    }
+}
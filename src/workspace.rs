@@ -0,0 +1,300 @@
+//! Named workspaces (boards), each stored in its own data file.
+
+use crate::attachments;
+use crate::i18n::{fill, Msg};
+use crate::lock::DataLock;
+use crate::storage::Storage;
+use crate::{AppData, ProductionManager};
+use eframe::egui;
+use serde::{Deserialize, Serialize};
+use std::fs;
+use std::path::{Path, PathBuf};
+
+const MAX_RECENT: usize = 5;
+
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq)]
+pub struct Workspace {
+    pub name: String,
+    /// Data file, relative to the data directory.
+    pub file: String,
+}
+
+impl Workspace {
+    /// The workspace every installation starts with. It keeps using the
+    /// original `data.json` so existing boards show up unchanged.
+    pub fn initial(name: &str) -> Self {
+        Self {
+            name: name.to_string(),
+            file: "data.json".to_string(),
+        }
+    }
+
+    fn new(name: &str) -> Self {
+        let slug: String = name
+            .chars()
+            .map(|c| if c.is_alphanumeric() { c } else { '_' })
+            .take(32)
+            .collect();
+        let id = uuid::Uuid::new_v4().simple().to_string();
+        Self {
+            name: name.to_string(),
+            file: format!("workspaces/{}-{}.json", slug, &id[..8]),
+        }
+    }
+
+    pub fn path(&self, data_dir: &Path) -> PathBuf {
+        data_dir.join(&self.file)
+    }
+}
+
+pub enum WorkspaceDialog {
+    Create { name: String },
+    Duplicate { name: String },
+    Delete { name: String },
+}
+
+impl ProductionManager {
+    fn find_workspace(&self, name: &str) -> Option<&Workspace> {
+        self.settings.workspaces.iter().find(|w| w.name == name)
+    }
+
+    fn current_workspace(&self) -> Option<&Workspace> {
+        self.find_workspace(&self.settings.current_workspace)
+    }
+
+    /// Makes sure the registry has at least one workspace and that the
+    /// current one exists.
+    pub(crate) fn ensure_workspaces(&mut self) {
+        if self.settings.workspaces.is_empty() {
+            let initial = Workspace::initial(self.t(Msg::DefaultWorkspaceName));
+            self.settings.workspaces.push(initial);
+        }
+        if self.current_workspace().is_none() {
+            self.settings.current_workspace = self.settings.workspaces[0].name.clone();
+        }
+    }
+
+    fn touch_recent(&mut self, name: &str) {
+        self.settings.recent_workspaces.retain(|n| n != name);
+        self.settings.recent_workspaces.insert(0, name.to_string());
+        self.settings.recent_workspaces.truncate(MAX_RECENT);
+    }
+
+    /// Switches to the named workspace, creating it if it doesn't exist yet.
+    pub(crate) fn open_workspace(&mut self, name: &str) {
+        if self.find_workspace(name).is_none() {
+            let workspace = Workspace::new(name);
+            if let Some(parent) = workspace.path(&self.data_dir).parent() {
+                fs::create_dir_all(parent).ok();
+            }
+            self.settings.workspaces.push(workspace);
+        }
+        let workspace = self.find_workspace(name).cloned().unwrap();
+        self.settings.current_workspace = workspace.name.clone();
+        self.touch_recent(&workspace.name);
+        self.save_settings();
+
+        self.set_data_path(workspace.path(&self.data_dir));
+        self.acquire_lock();
+        self.reload_data();
+        self.reset_transient_state();
+    }
+
+    fn create_workspace(&mut self, name: &str, data: AppData) {
+        let workspace = Workspace::new(name);
+        let path = workspace.path(&self.data_dir);
+        if let Some(parent) = path.parent() {
+            fs::create_dir_all(parent).ok();
+        }
+        if let Err(err) = Storage::open(&path).save(&data, &data) {
+            self.show_status(&fill(self.t(Msg::SaveFailed), &[&err]));
+            return;
+        }
+        self.settings.workspaces.push(workspace);
+        self.open_workspace(name);
+    }
+
+    fn delete_workspace(&mut self, name: &str) {
+        if self.settings.workspaces.len() <= 1 {
+            self.show_status(self.t(Msg::CannotDeleteLastWorkspace));
+            return;
+        }
+        let Some(workspace) = self.find_workspace(name).cloned() else {
+            return;
+        };
+        let path = workspace.path(&self.data_dir);
+        let is_current = self.settings.current_workspace == name;
+        if is_current && self.read_only {
+            self.show_status(self.t(Msg::WorkspaceInUse));
+            return;
+        }
+        // Held until the files are gone so no other window opens the board
+        // in between.
+        let _guard = if is_current {
+            self.lock.take()
+        } else {
+            match DataLock::acquire(&path) {
+                Ok(lock) => Some(lock),
+                Err(_) => {
+                    self.show_status(self.t(Msg::WorkspaceInUse));
+                    return;
+                }
+            }
+        };
+        if is_current {
+            // The writer's final flush would recreate the file.
+            self.saver = None;
+            self.storage = Storage::Json(PathBuf::new());
+            self.merge = None;
+        }
+        fs::remove_file(&path).ok();
+        fs::remove_dir_all(attachments::dir_for(&path)).ok();
+        self.settings.workspaces.retain(|w| w.name != name);
+        self.settings.recent_workspaces.retain(|n| n != name);
+        if is_current {
+            let next = self
+                .settings
+                .recent_workspaces
+                .first()
+                .cloned()
+                .unwrap_or_else(|| self.settings.workspaces[0].name.clone());
+            self.open_workspace(&next);
+        } else {
+            self.save_settings();
+        }
+        self.show_status(self.t(Msg::WorkspaceDeleted));
+    }
+
+    pub(crate) fn render_workspace_menu(&mut self, ui: &mut egui::Ui) {
+        let current = self.settings.current_workspace.clone();
+        let mut open = None;
+
+        ui.menu_button(format!("🗂 {}", current), |ui| {
+            let recent: Vec<String> = self
+                .settings
+                .recent_workspaces
+                .iter()
+                .filter(|n| **n != current)
+                .cloned()
+                .collect();
+            if !recent.is_empty() {
+                ui.label(egui::RichText::new(self.t(Msg::RecentWorkspaces)).small().weak());
+                for name in recent {
+                    if ui.button(&name).clicked() {
+                        open = Some(name);
+                        ui.close_menu();
+                    }
+                }
+                ui.separator();
+            }
+
+            ui.label(egui::RichText::new(self.t(Msg::AllWorkspaces)).small().weak());
+            for workspace in &self.settings.workspaces {
+                if ui.selectable_label(workspace.name == current, &workspace.name).clicked() {
+                    open = Some(workspace.name.clone());
+                    ui.close_menu();
+                }
+            }
+
+            ui.separator();
+            if ui.button(format!("➕ {}", self.t(Msg::NewWorkspace))).clicked() {
+                self.workspace_dialog = Some(WorkspaceDialog::Create { name: String::new() });
+                ui.close_menu();
+            }
+            if ui.button(format!("⧉ {}", self.t(Msg::DuplicateWorkspace))).clicked() {
+                let name = fill(self.t(Msg::CopyOfWorkspace), &[&current]);
+                self.workspace_dialog = Some(WorkspaceDialog::Duplicate { name });
+                ui.close_menu();
+            }
+            let can_delete = self.settings.workspaces.len() > 1;
+            if ui.add_enabled(can_delete, egui::Button::new(format!("🗑 {}", self.t(Msg::DeleteWorkspace)))).clicked() {
+                self.workspace_dialog = Some(WorkspaceDialog::Delete { name: current.clone() });
+                ui.close_menu();
+            }
+        });
+
+        if let Some(name) = open {
+            if name != current {
+                self.open_workspace(&name);
+                self.show_status(&fill(self.t(Msg::WorkspaceOpened), &[&name]));
+            }
+        }
+    }
+
+    pub(crate) fn render_workspace_dialog(&mut self, ctx: &egui::Context) {
+        let Some(mut dialog) = self.workspace_dialog.take() else {
+            return;
+        };
+        let title = match dialog {
+            WorkspaceDialog::Create { .. } => self.t(Msg::NewWorkspace),
+            WorkspaceDialog::Duplicate { .. } => self.t(Msg::DuplicateWorkspace),
+            WorkspaceDialog::Delete { .. } => self.t(Msg::DeleteWorkspace),
+        };
+        let duplicate = matches!(dialog, WorkspaceDialog::Duplicate { .. });
+        let mut keep_open = true;
+
+        egui::Window::new(title)
+            .id(egui::Id::new("workspace_dialog"))
+            .collapsible(false)
+            .resizable(false)
+            .anchor(egui::Align2::CENTER_CENTER, [0.0, 0.0])
+            .show(ctx, |ui| match &mut dialog {
+                WorkspaceDialog::Create { name } | WorkspaceDialog::Duplicate { name } => {
+                    ui.horizontal(|ui| {
+                        ui.label(self.t(Msg::WorkspaceNameLabel));
+                        ui.add_sized([220.0, 20.0], egui::TextEdit::singleline(name));
+                    });
+                    let trimmed = name.trim().to_string();
+                    let exists = self.find_workspace(&trimmed).is_some();
+                    if exists {
+                        ui.colored_label(egui::Color32::from_rgb(220, 120, 100), self.t(Msg::WorkspaceExists));
+                    }
+
+                    ui.add_space(12.0);
+
+                    ui.horizontal(|ui| {
+                        if ui.button(self.t(Msg::Cancel)).clicked() {
+                            keep_open = false;
+                        }
+                        ui.add_space(20.0);
+                        let can_create = !trimmed.is_empty() && !exists;
+                        if ui.add_enabled(can_create, egui::Button::new(self.t(Msg::Create))).clicked() {
+                            let data = if duplicate {
+                                self.data.clone()
+                            } else {
+                                AppData::with_language(self.settings.language)
+                            };
+                            let attachments = duplicate.then(|| self.attachments_dir());
+                            self.create_workspace(&trimmed, data);
+                            if let Some(from) = attachments {
+                                attachments::copy_all(&from, &self.attachments_dir());
+                            }
+                            self.show_status(&fill(self.t(Msg::WorkspaceOpened), &[&trimmed]));
+                            keep_open = false;
+                        }
+                    });
+                }
+                WorkspaceDialog::Delete { name } => {
+                    ui.label(fill(self.t(Msg::DeleteWorkspaceConfirm), &[name]));
+
+                    ui.add_space(12.0);
+
+                    ui.horizontal(|ui| {
+                        if ui.button(self.t(Msg::Cancel)).clicked() {
+                            keep_open = false;
+                        }
+                        ui.add_space(20.0);
+                        if ui.button(self.t(Msg::Delete)).clicked() {
+                            let name = name.clone();
+                            self.delete_workspace(&name);
+                            keep_open = false;
+                        }
+                    });
+                }
+            });
+
+        if keep_open {
+            self.workspace_dialog = Some(dialog);
+        }
+    }
+}